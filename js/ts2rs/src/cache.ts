@@ -0,0 +1,122 @@
+/**
+ * On-disk cache of resolved types, so repeated conversions only re-resolve what changed
+ */
+
+import * as crypto from "node:crypto";
+import * as fs from "node:fs";
+import * as path from "node:path";
+import { ts } from "ts-morph";
+import type { CollectedType, ConversionOptions, Diagnostic, TypeSelection } from "./types";
+import { getGlobalFileNames, getReferencedProjects, parseTsConfig, resolveModule } from "./module-resolution";
+
+/**
+ * Bumped whenever the intermediate representation or the resolution changes, invalidating existing caches
+ */
+const CACHE_VERSION = 11;
+
+/**
+ * Options that don't change the resolved types, and so don't invalidate the cache
+ */
+const IGNORED_OPTIONS: (keyof ConversionOptions)[] = [
+  "outputPath",
+  "dryRun",
+  "checkOnly",
+  "verbose",
+  "cacheDir",
+  "manifest",
+  "sourceComments",
+  "keepRegions",
+];
+
+/**
+ * What happened while resolving a type, in order, so it can be replayed
+ */
+export type ResolutionEvent =
+  | { resolve: string } // Resolving a referenced type, by its type key
+  | { collect: string; type: CollectedType } // Collecting a type under a type key
+  | { exclude: string; rustType?: string } // Excluding a type (`@ts2rs-exclude`)
+  | { warning: Diagnostic };
+
+export interface CacheData {
+  version: number;
+  config: string; // Hash of the tsconfig's compiler options and of the files it loads without imports
+  files: Record<string, string>; // File the types were resolved from => hash of its content
+  globalFiles: string[]; // Declaration files that may affect any file
+  unresolvedImports: { file: string; specifier: string }[]; // Imports that didn't resolve to a file
+  records: Record<string, ResolutionEvent[]>; // Type key => its resolution
+  result: { types: CollectedType[]; warnings: Diagnostic[]; selection: TypeSelection[] }; // Output of the resolver
+}
+
+export class ResolutionCache {
+  private cachePath: string;
+
+  constructor(cacheDir: string, options: ConversionOptions) {
+    const key = Object.fromEntries(
+      Object.entries(options).filter(([name]) => !IGNORED_OPTIONS.includes(name as keyof ConversionOptions)),
+    );
+    this.cachePath = path.join(cacheDir, `${hash(JSON.stringify(key))}.json`);
+  }
+
+  /**
+   * The cached data, unless missing or written by another version of ts2rs
+   */
+  load(): CacheData | undefined {
+    try {
+      const data = JSON.parse(fs.readFileSync(this.cachePath, "utf-8")) as CacheData;
+      return data.version === CACHE_VERSION ? data : undefined;
+    } catch {
+      return undefined;
+    }
+  }
+
+  /**
+   * The result of the previous run if no file it loaded (nor the tsconfig) changed since, no declaration or
+   * augmenting file was added to the tsconfig, and no import that didn't resolve resolves now
+   */
+  getUnchangedResult(data: CacheData, tsConfigPath: string | undefined): CacheData["result"] | undefined {
+    if (data.config !== getConfigHash(tsConfigPath)) {
+      return undefined;
+    }
+    for (const [file, fileHash] of Object.entries(data.files)) {
+      if (getFileHash(file) !== fileHash) {
+        return undefined;
+      }
+    }
+    if (data.unresolvedImports.length > 0) {
+      const options = (tsConfigPath ? parseTsConfig(tsConfigPath)?.options : undefined) ?? {};
+      const projects = tsConfigPath ? getReferencedProjects(tsConfigPath) : [];
+      if (data.unresolvedImports.some(({ file, specifier }) => resolveModule(specifier, file, options, projects))) {
+        return undefined;
+      }
+    }
+    return data.result;
+  }
+
+  save(data: Omit<CacheData, "version">): void {
+    fs.mkdirSync(path.dirname(this.cachePath), { recursive: true });
+    fs.writeFileSync(this.cachePath, JSON.stringify({ version: CACHE_VERSION, ...data }));
+  }
+}
+
+/**
+ * Remove all cached conversions
+ */
+export function clearCache(cacheDir: string): void {
+  fs.rmSync(cacheDir, { recursive: true, force: true });
+}
+
+export function getConfigHash(tsConfigPath: string | undefined): string {
+  const config = tsConfigPath
+    ? { options: parseTsConfig(tsConfigPath)?.options ?? null, globalFiles: getGlobalFileNames(tsConfigPath) }
+    : null;
+  return hash(JSON.stringify(config));
+}
+
+export function getFileHash(filePath: string): string | undefined {
+  const content = ts.sys.readFile(filePath);
+  return content === undefined ? undefined : hash(content);
+}
+
+function hash(content: string): string {
+  return crypto.createHash("sha256").update(content).digest("hex");
+}
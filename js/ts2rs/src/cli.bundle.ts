@@ -9,8 +9,326 @@
 import { Command } from "commander";
 import * as path from "node:path";
 import * as fs from "node:fs";
-import { convert } from "./index";
-import type { CustomTypeMappingValue } from "./types";
+import {
+  clearCache,
+  DEFAULT_LINT_LEVELS,
+  CONFIG_FILE_NAMES,
+  convert,
+  convertProject,
+  findConfig,
+  formatDiagnostic,
+  loadConfig,
+  TypeConversionError,
+  verify,
+} from "./index";
+import { watchProject, type WatchRun } from "./watch";
+import type { ProjectConfig } from "./config";
+import type {
+  ConversionOptions,
+  ConversionResult,
+  CustomTypeMappingValue,
+  LintLevel,
+  LintName,
+  TypeSelection,
+} from "./types";
+
+/**
+ * CLI options setting conversion options, which a config file sets instead
+ */
+const CONVERSION_FLAGS: Record<string, string> = {
+  input: "--input",
+  output: "--output",
+  tsconfig: "--tsconfig",
+  outDir: "--out-dir",
+  outModule: "--out-module",
+  types: "--types",
+  mapping: "--mapping",
+  customHeader: "--custom-header",
+  customHeaderFile: "--custom-header-file",
+  customFooter: "--custom-footer",
+  customFooterFile: "--custom-footer-file",
+  annotation: "--annotation",
+  strict: "--strict",
+  allow: "--allow",
+  warn: "--warn",
+  deny: "--deny",
+  classMembers: "--class-members",
+  rename: "--rename",
+  packageCrate: "--package-crate",
+  namespaces: "--namespaces",
+  templateLiteralNewtypes: "--template-literal-newtypes",
+  manifest: "--manifest",
+  sourceComments: "--source-comments",
+  keepRegions: "--keep-regions",
+};
+
+/**
+ * Print the stats, warnings and external types of a conversion to stderr
+ */
+function printReport(result: ConversionResult, prefix = ""): void {
+  if (result.stats) {
+    const { stats } = result;
+    console.error(
+      `${prefix}Loaded ${stats.sourceFiles} file(s) in ${stats.loadMs.toFixed(0)}ms, ` +
+        `resolved ${stats.types} type(s) (${stats.reusedTypes} from the cache) in ${stats.resolveMs.toFixed(0)}ms, ` +
+        `generated in ${stats.generateMs.toFixed(0)}ms, written in ${stats.writeMs.toFixed(0)}ms`,
+    );
+    console.error("");
+  }
+
+  for (const warning of result.warnings) {
+    console.error(`${prefix}${formatDiagnostic(warning)}`);
+    console.error("");
+  }
+
+  if (result.externalTypes.length > 0) {
+    console.error(`${prefix}External types:`);
+    for (const external of result.externalTypes) {
+      const source = external.package ? ` (${external.package})` : "";
+      console.error(`  - ${external.rustPath}${source}, used by ${external.usedBy.join(", ")}`);
+    }
+    console.error("");
+  }
+}
+
+/**
+ * The report of a conversion for --format json: its diagnostics and what it converted, generated or found out of date
+ */
+function getJsonReport(result: ConversionResult): Record<string, unknown> {
+  return {
+    diagnostics: result.warnings,
+    convertedTypes: result.convertedTypes,
+    externalTypes: result.externalTypes,
+    ...(result.stats ? { stats: result.stats } : {}),
+    ...(result.selection ? { selection: result.selection } : {}),
+    ...(result.drift ? { drift: result.drift } : {}),
+    ...(result.writtenFiles ? { writtenFiles: result.writtenFiles } : {}),
+  };
+}
+
+/**
+ * The report of a failed conversion for --format json, with the error as a diagnostic when it has one
+ */
+function getJsonError(error: unknown): Record<string, unknown> {
+  return {
+    error: error instanceof Error ? error.message : String(error),
+    diagnostics: error instanceof TypeConversionError ? error.errors : [],
+  };
+}
+
+/**
+ * Print an error: the errors of a conversion like rustc does, others as their message
+ */
+function printError(error: unknown, prefix = ""): void {
+  if (error instanceof TypeConversionError) {
+    console.error(error.errors.map((diagnostic) => `${prefix}${formatDiagnostic(diagnostic)}`).join("\n\n"));
+  } else {
+    console.error(`${prefix}Error: ${error instanceof Error ? error.message : "Unknown error occurred"}`);
+  }
+}
+
+/**
+ * Accumulate the values of a repeatable option taking comma-separated lists (`-D value-fallback -D lossy-number`)
+ */
+function collectList(value: string, previous: string[] = []): string[] {
+  return [...previous, ...value.split(",").map((item) => item.trim()).filter((item) => item)];
+}
+
+/**
+ * The lint levels of --allow, --warn and --deny, the strictest winning for a lint given to several of them
+ */
+function getLints(options: Record<string, any>): Partial<Record<LintName, LintLevel>> | undefined {
+  const lints: Partial<Record<LintName, LintLevel>> = {};
+  for (const level of ["allow", "warn", "deny"] as const) {
+    for (const lint of options[level] ?? []) {
+      if (!(lint in DEFAULT_LINT_LEVELS)) {
+        throw new Error(`Unknown lint '${lint}' (expected ${Object.keys(DEFAULT_LINT_LEVELS).join(", ")})`);
+      }
+      lints[lint as LintName] = level;
+    }
+  }
+  return Object.keys(lints).length > 0 ? lints : undefined;
+}
+
+const SELECTION_REASONS: Record<TypeSelection["reason"], (detail?: string) => string> = {
+  listed: () => "listed",
+  exported: () => "exported",
+  pattern: (pattern) => `matched ${pattern}`,
+  dependency: (type) => `referenced by ${type}`,
+  excluded: (rustType) => `excluded with @ts2rs-exclude, referenced as ${rustType}`,
+};
+
+/**
+ * Print the types selected for a conversion and why, for --dry-run
+ */
+function printSelection(result: ConversionResult, prefix = ""): void {
+  console.log(`${prefix}Selected types:`);
+  for (const { name, sourceFile, reason, detail } of result.selection ?? []) {
+    console.log(`  - ${name} (${path.relative(process.cwd(), sourceFile)}): ${SELECTION_REASONS[reason](detail)}`);
+  }
+  console.log("");
+}
+
+/**
+ * Print the diffs of the output files that aren't up to date, for --check, returning whether all are
+ */
+function printDrift(result: ConversionResult, prefix = ""): boolean {
+  const drift = result.drift ?? [];
+  for (const { diff } of drift) {
+    console.log(diff);
+  }
+  if (drift.length > 0) {
+    console.error(`✗ ${prefix}${drift.length} file(s) out of date, run ts2rs without --check to update them`);
+    return false;
+  }
+  console.log(`✓ ${prefix}Generated files are up to date`);
+  return true;
+}
+
+/**
+ * Write the files the conversions read, one per line, for --inputs-file (e.g. for `cargo:rerun-if-changed`)
+ */
+function writeInputsFile(inputsFile: string, results: ConversionResult[]): void {
+  const inputFiles = new Set(results.flatMap((result) => result.inputFiles ?? []));
+  fs.mkdirSync(path.dirname(inputsFile), { recursive: true });
+  fs.writeFileSync(inputsFile, [...inputFiles].map((file) => `${file}\n`).join(""));
+}
+
+/**
+ * Compare a hand-written Rust file with the TypeScript types, for --verify, failing if they differ
+ */
+async function runVerify(options: ConversionOptions, rustFile: string, format: string): Promise<void> {
+  const result = await verify(options, rustFile);
+  if (format === "json") {
+    console.log(JSON.stringify({
+      diagnostics: [...result.warnings, ...result.mismatches],
+      verifiedTypes: result.verifiedTypes,
+    }, null, 2));
+  } else {
+    for (const diagnostic of [...result.warnings, ...result.mismatches]) {
+      console.error(formatDiagnostic(diagnostic));
+      console.error("");
+    }
+    const file = path.relative(process.cwd(), rustFile);
+    if (result.mismatches.length > 0) {
+      console.error(`✗ ${result.mismatches.length} difference(s) between the TypeScript types and ${file}`);
+    } else {
+      console.log(`✓ ${result.verifiedTypes.length} type(s) of ${file} match the TypeScript types`);
+    }
+  }
+  if (result.mismatches.length > 0) {
+    process.exit(1);
+  }
+}
+
+/**
+ * Print the outcome of a --watch run, replacing the previous one, or as a line of JSON with --format json
+ */
+function printWatchRun(run: WatchRun, format: string): void {
+  if (format === "json") {
+    console.log(JSON.stringify({
+      changedFiles: run.changedFiles,
+      jobs: [
+        ...run.results.map(({ name, result }) => ({ name, ...getJsonReport(result) })),
+        ...run.errors.map(({ name, error }) => ({ name, ...getJsonError(error) })),
+      ],
+    }));
+    return;
+  }
+  if (process.stdout.isTTY) {
+    console.clear();
+  }
+  const time = new Date().toLocaleTimeString();
+  if (run.changedFiles.length > 0) {
+    console.log(`[${time}] Changed: ${run.changedFiles.map((file) => path.relative(process.cwd(), file)).join(", ")}\n`);
+  }
+  for (const { name, result } of run.results) {
+    printReport(result, `[${name}] `);
+    const written = result.writtenFiles ?? [];
+    console.log(written.length > 0 ? `✓ [${name}] Updated ${written.length} file(s)` : `✓ [${name}] Up to date`);
+  }
+  for (const { name, error } of run.errors) {
+    printError(error, `✗ [${name}] `);
+  }
+  console.log(`\n[${time}] Watching for changes...`);
+}
+
+/**
+ * Convert, then convert again on changes until interrupted
+ */
+async function runWatch(
+  config: ProjectConfig,
+  configDir: string,
+  overrides: Partial<ConversionOptions>,
+  format: string,
+): Promise<void> {
+  for (const job of config.jobs) {
+    if (!job.outputPath && !job.outputDir) {
+      throw new Error("--watch requires --output or --out-dir");
+    }
+  }
+  await watchProject(config, configDir, overrides, (run) => printWatchRun(run, format));
+}
+
+/**
+ * Run the jobs of a config file
+ */
+async function runConfig(configPath: string, options: Record<string, any>): Promise<void> {
+  for (const [option, flag] of Object.entries(CONVERSION_FLAGS)) {
+    if (options[option] !== undefined) {
+      throw new Error(`${flag} can't be used with a config file, set it in ${configPath}`);
+    }
+  }
+
+  const config = await loadConfig(configPath);
+  const cacheDir = options["cacheDir"] ? path.resolve(process.cwd(), options["cacheDir"]) : undefined;
+  if (options["clearCache"]) {
+    const cacheDirs = cacheDir ? [cacheDir] : [config.cacheDir, ...config.jobs.map((job) => job.cacheDir)];
+    for (const dir of new Set(cacheDirs.filter((d): d is string => d !== undefined))) {
+      clearCache(dir);
+    }
+  }
+
+  const overrides = {
+    ...(options["verbose"] ? { verbose: true } : {}),
+    ...(options["dryRun"] ? { dryRun: true } : {}),
+    ...(options["check"] ? { checkOnly: true } : {}),
+    ...(cacheDir ? { cacheDir } : {}),
+    ...(options["cache"] === false ? { cacheDir: undefined } : {}),
+  };
+  if (options["watch"]) {
+    await runWatch(config, path.dirname(configPath), overrides, options["format"]);
+    return;
+  }
+
+  const jobs = await convertProject(config, path.dirname(configPath), overrides);
+  if (options["inputsFile"]) {
+    writeInputsFile(path.resolve(process.cwd(), options["inputsFile"]), jobs.map((job) => job.result));
+  }
+  if (options["format"] === "json") {
+    console.log(JSON.stringify({ jobs: jobs.map(({ name, result }) => ({ name, ...getJsonReport(result) })) }, null, 2));
+    if (jobs.some(({ result }) => (result.drift ?? []).length > 0)) {
+      process.exit(1);
+    }
+    return;
+  }
+  let upToDate = true;
+  for (const { name, options: jobOptions, result } of jobs) {
+    printReport(result, `[${name}] `);
+    if (options["dryRun"]) {
+      printSelection(result, `[${name}] `);
+      continue;
+    }
+    if (options["check"]) {
+      upToDate = printDrift(result, `[${name}] `) && upToDate;
+      continue;
+    }
+    console.log(`✓ [${name}] Generated Rust types for ${result.convertedTypes.length} type(s) in ${jobOptions.outputDir ?? jobOptions.outputPath}`);
+  }
+  if (!upToDate) {
+    process.exit(1);
+  }
+}
 
 const program = new Command();
 
@@ -20,11 +338,27 @@ program
     "Convert TypeScript types to Rust types for bidirectional JSON serialization",
   )
   .version("0.0.1")
-  .requiredOption("-i, --input <path>", "Path to the TypeScript entry file")
+  .option("-i, --input <path>", "Path to the TypeScript entry file")
+  .option(
+    "-c, --config <path>",
+    `Run the jobs of a config file instead of a single conversion (defaults to ${CONFIG_FILE_NAMES.join(" or ")} of the working directory when --input is not given)`,
+  )
   .option("-o, --output <path>", "Output path for the generated Rust file")
+  .option(
+    "--tsconfig <path>",
+    "tsconfig used to resolve modules (paths, project references, ...), defaults to the nearest tsconfig.json of the input file",
+  )
+  .option(
+    "-d, --out-dir <path>",
+    "Output directory for one Rust module per TypeScript source file, with a mod.rs re-exporting their types",
+  )
+  .option(
+    "--out-module <path>",
+    "Rust path of the --out-dir module, used for imports between modules (defaults to the path derived from the directory's location under src, e.g. crate::generated)",
+  )
   .option(
     "-t, --types <names>",
-    "Comma-separated list of type names to convert (defaults to all exported types)",
+    "Comma-separated list of type names or patterns to convert, e.g. 'Api*,!*Internal,*Request|*Response' (defaults to all exported types)",
   )
   .option(
     "-m, --mapping <mappings>",
@@ -54,12 +388,104 @@ program
     "-s, --strict",
     "Strict mode: fail on unresolvable types instead of falling back to serde_json::Value",
   )
+  .option(
+    "-A, --allow <lints>",
+    `Don't report these lints (comma-separated, repeatable): ${Object.keys(DEFAULT_LINT_LEVELS).join(", ")}`,
+    collectList,
+  )
+  .option("-W, --warn <lints>", "Report these lints as warnings (comma-separated, repeatable)", collectList)
+  .option("-D, --deny <lints>", "Fail on these lints, e.g. -D value-fallback (comma-separated, repeatable)", collectList)
+  .option(
+    "--class-members <visibility>",
+    "Class members converted to struct fields: public (default), protected (public and protected) or all",
+  )
+  .option(
+    "-r, --rename <renames>",
+    "Rename types in format file#TypeName=RustName,... (comma-separated), e.g. to resolve same-named types from different files",
+  )
+  .option(
+    "-p, --package-crate <mappings>",
+    "Reference types from npm packages as types of existing Rust crates instead of generating them, in format package=rust::module,... (comma-separated)",
+  )
+  .option(
+    "--namespaces <mode>",
+    "How types inside namespaces are emitted: modules (default, nested pub mod blocks) or flat (names prefixed with the namespaces)",
+  )
+  .option(
+    "--template-literal-newtypes",
+    "Generate template literal types as newtypes that validate their pattern on deserialization instead of String aliases",
+  )
+  .option(
+    "--manifest",
+    "Also write a JSON manifest of the TypeScript declaration each Rust item was converted from (types.manifest.json next to --output, manifest.json in --out-dir)",
+  )
+  .option(
+    "--source-comments",
+    "Comment each generated item, field and variant with where it is declared, and each serde_json::Value fallback with its reason",
+  )
+  .option(
+    "--keep-regions",
+    "Keep the code between // ts2rs:keep-start and // ts2rs:keep-end when generating again, with an empty region after each type",
+  )
+  .option("--cache-dir <path>", "Cache resolved types in this directory, re-resolving only types whose files changed")
+  .option("--clear-cache", "Clear the --cache-dir cache before converting")
+  .option("--no-cache", "Don't read nor write the --cache-dir cache")
+  .option("--dry-run", "List the types that would be converted and why, without writing anything")
+  .option("--check", "Check that the output files are up to date instead of writing them, printing a diff and failing if not")
+  .option("--inputs-file <path>", "Write the files the types were resolved from to this file, one per line")
+  .option(
+    "--verify <rust-file>",
+    "Compare the structs and enums of a hand-written Rust file with the TypeScript types instead of generating code, failing if their JSON differs",
+  )
+  .option("-w, --watch", "Convert again when the TypeScript files the types were resolved from change")
+  .option("-v, --verbose", "Print timings and the number of loaded files to stderr")
+  .option(
+    "--format <format>",
+    "Report format: human (default, warnings on stderr like rustc) or json (a report with the diagnostics on stdout)",
+    "human",
+  )
   .action(async (options) => {
     try {
+      if (!["human", "json"].includes(options.format)) {
+        throw new Error(`Invalid --format value '${options.format}' (expected human or json)`);
+      }
+      if (options.watch && (options.dryRun || options.check)) {
+        throw new Error("--watch can't be used with --dry-run or --check");
+      }
+      if (options.verify && (options.output || options.outDir || options.watch || options.dryRun || options.check)) {
+        throw new Error("--verify can't be used with --output, --out-dir, --watch, --dry-run or --check");
+      }
+      const configPath = options.config
+        ? path.resolve(process.cwd(), options.config)
+        : options.input ? undefined : findConfig(process.cwd());
+      if (configPath) {
+        if (options.verify) {
+          throw new Error("--verify can't be used with a config file, select the types with --input and --types");
+        }
+        await runConfig(configPath, options);
+        return;
+      }
+      if (!options.input) {
+        throw new Error(`--input is required without a config file (${CONFIG_FILE_NAMES.join(" or ")})`);
+      }
+
       const inputPath = path.resolve(process.cwd(), options.input);
       const outputPath = options.output
         ? path.resolve(process.cwd(), options.output)
         : undefined;
+      const outputDir = options.outDir
+        ? path.resolve(process.cwd(), options.outDir)
+        : undefined;
+
+      if (outputPath && outputDir) {
+        throw new Error("--output and --out-dir cannot be used together");
+      }
+      if (options.check && !outputPath && !outputDir) {
+        throw new Error("--check requires --output or --out-dir");
+      }
+      if (options.manifest && !outputPath && !outputDir) {
+        throw new Error("--manifest requires --output or --out-dir");
+      }
 
       const typeNames = options.types
         ? options.types.split(",").map((t: string) => t.trim())
@@ -95,6 +521,32 @@ program
         }
       }
 
+      let typeRenames: Record<string, string> | undefined;
+      if (options.rename) {
+        typeRenames = {};
+        for (const rename of options.rename.split(",")) {
+          // Format: file#TypeName=RustName or TypeName=RustName
+          const equalsIndex = rename.lastIndexOf("=");
+          if (equalsIndex === -1) {
+            throw new Error(`Invalid --rename entry '${rename}' (expected file#TypeName=RustName)`);
+          }
+          typeRenames[rename.slice(0, equalsIndex).trim()] = rename.slice(equalsIndex + 1).trim();
+        }
+      }
+
+      let packageCrates: Record<string, string> | undefined;
+      if (options.packageCrate) {
+        packageCrates = {};
+        for (const mapping of options.packageCrate.split(",")) {
+          // Format: package=rust::module
+          const equalsIndex = mapping.lastIndexOf("=");
+          if (equalsIndex === -1) {
+            throw new Error(`Invalid --package-crate entry '${mapping}' (expected package=rust::module)`);
+          }
+          packageCrates[mapping.slice(0, equalsIndex).trim()] = mapping.slice(equalsIndex + 1).trim();
+        }
+      }
+
       // Handle custom header
       let customHeader = options.customHeader;
       if (options.customHeaderFile) {
@@ -109,30 +561,92 @@ program
         customFooter = fs.readFileSync(footerPath, "utf-8").trim();
       }
 
+      if (options.classMembers && !["public", "protected", "all"].includes(options.classMembers)) {
+        throw new Error(`Invalid --class-members value '${options.classMembers}' (expected public, protected or all)`);
+      }
+
+      if (options.namespaces && !["modules", "flat"].includes(options.namespaces)) {
+        throw new Error(`Invalid --namespaces value '${options.namespaces}' (expected modules or flat)`);
+      }
+
+      const cacheDir = options.cacheDir && options.cache
+        ? path.resolve(process.cwd(), options.cacheDir)
+        : undefined;
+      if (options.clearCache) {
+        if (!options.cacheDir) {
+          throw new Error("--clear-cache requires --cache-dir");
+        }
+        clearCache(path.resolve(process.cwd(), options.cacheDir));
+      }
+
       const customTypeAnnotations = options.annotation
         ? options.annotation.split(",").map((a: string) => a.trim())
         : undefined;
 
-      const result = await convert({
+      const conversionOptions: ConversionOptions = {
         entryFile: inputPath,
+        tsConfigPath: options.tsconfig ? path.resolve(process.cwd(), options.tsconfig) : undefined,
         outputPath,
+        outputDir,
+        outputModulePath: options.outModule,
         typeNames,
         customTypeMappings,
         customHeader,
         customFooter,
         customTypeAnnotations,
         strict: options.strict,
-      });
+        lints: getLints(options),
+        templateLiteralNewtypes: options.templateLiteralNewtypes,
+        classMemberVisibility: options.classMembers,
+        namespaces: options.namespaces,
+        typeRenames,
+        packageCrates,
+        verbose: options.verbose,
+        dryRun: options.dryRun,
+        checkOnly: options.check,
+        cacheDir,
+        manifest: options.manifest,
+        sourceComments: options.sourceComments,
+        keepRegions: options.keepRegions,
+      };
+      if (options.verify) {
+        await runVerify(conversionOptions, path.resolve(process.cwd(), options.verify), options.format);
+        return;
+      }
+      if (options.watch) {
+        await runWatch({ jobs: [conversionOptions] }, process.cwd(), {}, options.format);
+        return;
+      }
 
-      if (result.warnings.length > 0) {
-        console.error("Warnings:");
-        for (const warning of result.warnings) {
-          console.error(`  - ${warning}`);
+      const result = await convert(conversionOptions);
+      if (options.inputsFile) {
+        writeInputsFile(path.resolve(process.cwd(), options.inputsFile), [result]);
+      }
+
+      if (options.format === "json") {
+        const rustCode = outputPath || outputDir || options.dryRun ? {} : { rustCode: result.rustCode };
+        console.log(JSON.stringify({ ...getJsonReport(result), ...rustCode }, null, 2));
+        if ((result.drift ?? []).length > 0) {
+          process.exit(1);
         }
-        console.error("");
+        return;
       }
 
-      if (!outputPath) {
+      printReport(result);
+
+      if (options.dryRun) {
+        printSelection(result);
+      } else if (options.check) {
+        if (!printDrift(result)) {
+          process.exit(1);
+        }
+      } else if (outputDir) {
+        console.log(`✓ Generated Rust types for ${result.convertedTypes.length} type(s) in ${Object.keys(result.files ?? {}).length} file(s):`);
+        for (const file of Object.keys(result.files ?? {})) {
+          console.log(`  - ${file}`);
+        }
+        console.log(`\n✓ Output written to: ${outputDir}`);
+      } else if (!outputPath) {
         console.log(result.rustCode);
       } else {
         console.log(`✓ Generated Rust types for ${result.convertedTypes.length} type(s):`);
@@ -142,13 +656,13 @@ program
         console.log(`\n✓ Output written to: ${outputPath}`);
       }
     } catch (error) {
-      if (error instanceof Error) {
-        console.error(`Error: ${error.message}`);
-        if (process.env["DEBUG"]) {
-          console.error(error.stack);
-        }
+      if (options.format === "json") {
+        console.log(JSON.stringify(getJsonError(error), null, 2));
       } else {
-        console.error("Unknown error occurred");
+        printError(error);
+      }
+      if (error instanceof Error && process.env["DEBUG"]) {
+        console.error(error.stack);
       }
       process.exit(1);
     }
@@ -0,0 +1,280 @@
+/**
+ * Project configuration files (`ts2rs.config.json` or `ts2rs.config.ts`) describing several conversions (jobs)
+ */
+
+import * as fs from "node:fs";
+import * as path from "node:path";
+import { pathToFileURL } from "node:url";
+import { DEFAULT_LINT_LEVELS, isLintName } from "./diagnostics";
+import type { ConversionOptions } from "./types";
+
+/**
+ * Options shared by the jobs of a project configuration, which jobs can override
+ */
+export type JobOptions = Omit<ConversionOptions, "entryFile" | "typeNames" | "outputPath" | "outputDir" | "sharedTypes">;
+
+export interface JobConfig extends JobOptions {
+  name?: string; // Shown in reports, defaults to the output path
+  entryFile: string;
+  typeNames?: string[];
+  outputPath?: string;
+  outputDir?: string;
+}
+
+/**
+ * Options applied to every job (`customTypeMappings`, `typeRenames` and `packageCrates` are merged with those of
+ * the jobs, other options of a job replace them), and the jobs run in order. Paths are relative to the config file.
+ */
+export interface ProjectConfig extends JobOptions {
+  jobs: JobConfig[];
+}
+
+/**
+ * Config files looked up in the working directory, in order
+ */
+export const CONFIG_FILE_NAMES: string[] = ["ts2rs.config.ts", "ts2rs.config.json"];
+
+/**
+ * Type a `ts2rs.config.ts` (`export default defineConfig({ jobs: [...] })`)
+ */
+export function defineConfig(config: ProjectConfig): ProjectConfig {
+  return config;
+}
+
+export function findConfig(dir: string): string | undefined {
+  return CONFIG_FILE_NAMES.map((name) => path.join(dir, name)).find((file) => fs.existsSync(file));
+}
+
+/**
+ * Load and validate a config file, with the paths of its options resolved
+ */
+export async function loadConfig(configPath: string): Promise<ProjectConfig> {
+  if (!fs.existsSync(configPath)) {
+    throw new Error(`Config file not found: ${configPath}`);
+  }
+
+  let value: unknown;
+  if (configPath.endsWith(".json")) {
+    try {
+      value = JSON.parse(fs.readFileSync(configPath, "utf-8"));
+    } catch (error) {
+      throw new Error(`Invalid config ${configPath}: ${error instanceof Error ? error.message : error}`);
+    }
+  } else {
+    value = (await import(pathToFileURL(configPath).href)).default;
+  }
+
+  const errors = validateConfig(value);
+  if (errors.length > 0) {
+    throw new Error(`Invalid config ${configPath}:\n${errors.map((e) => `  - ${e}`).join("\n")}`);
+  }
+  return resolveConfigPaths(value as ProjectConfig, path.dirname(configPath));
+}
+
+/**
+ * The options of a job: the shared options with the job's applied over them
+ */
+export function getJobOptions(config: ProjectConfig, job: JobConfig): ConversionOptions {
+  const { jobs: _, ...defaults } = config;
+  const { name: __, ...options } = job;
+  const merged: ConversionOptions = { ...defaults, ...options };
+  if (defaults.customTypeMappings && options.customTypeMappings) {
+    merged.customTypeMappings = { ...defaults.customTypeMappings, ...options.customTypeMappings };
+  }
+  if (defaults.typeRenames && options.typeRenames) {
+    merged.typeRenames = { ...defaults.typeRenames, ...options.typeRenames };
+  }
+  if (defaults.packageCrates && options.packageCrates) {
+    merged.packageCrates = { ...defaults.packageCrates, ...options.packageCrates };
+  }
+  return merged;
+}
+
+export function getJobName(job: JobConfig, configDir: string): string {
+  return job.name ?? path.relative(configDir, (job.outputDir ?? job.outputPath)!);
+}
+
+type Check = (value: unknown, at: string, errors: string[]) => void;
+
+const isString: Check = (value, at, errors) => {
+  if (typeof value !== "string") {
+    errors.push(`${at} must be a string`);
+  }
+};
+
+const isBoolean: Check = (value, at, errors) => {
+  if (typeof value !== "boolean") {
+    errors.push(`${at} must be a boolean`);
+  }
+};
+
+const isStringArray: Check = (value, at, errors) => {
+  if (!Array.isArray(value)) {
+    errors.push(`${at} must be an array of strings`);
+    return;
+  }
+  value.forEach((item, i) => isString(item, `${at}[${i}]`, errors));
+};
+
+const isStringRecord: Check = (value, at, errors) => {
+  if (!isObject(value)) {
+    errors.push(`${at} must be an object of strings`);
+    return;
+  }
+  for (const [key, item] of Object.entries(value)) {
+    isString(item, `${at}["${key}"]`, errors);
+  }
+};
+
+const isOneOf = (...values: string[]): Check => (value, at, errors) => {
+  if (typeof value !== "string" || !values.includes(value)) {
+    errors.push(`${at} must be one of ${values.map((v) => `"${v}"`).join(", ")}`);
+  }
+};
+
+const isTypeMappings: Check = (value, at, errors) => {
+  if (!isObject(value)) {
+    errors.push(`${at} must be an object`);
+    return;
+  }
+  for (const [key, mapping] of Object.entries(value)) {
+    const mappingAt = `${at}["${key}"]`;
+    if (typeof mapping === "string") {
+      continue;
+    }
+    if (!isObject(mapping)) {
+      errors.push(`${mappingAt} must be a Rust type or { rustType, fieldAnnotations }`);
+      continue;
+    }
+    checkKeys(mapping, mappingAt, { rustType: isString, fieldAnnotations: isStringArray }, errors);
+    if (mapping["rustType"] === undefined) {
+      errors.push(`${mappingAt}.rustType is required`);
+    }
+  }
+};
+
+const isLints: Check = (value, at, errors) => {
+  if (!isObject(value)) {
+    errors.push(`${at} must be an object`);
+    return;
+  }
+  for (const [lint, level] of Object.entries(value)) {
+    if (!isLintName(lint)) {
+      errors.push(`${at}["${lint}"] is not a lint, expected one of ${Object.keys(DEFAULT_LINT_LEVELS).join(", ")}`);
+    } else {
+      isOneOf("allow", "warn", "deny")(level, `${at}["${lint}"]`, errors);
+    }
+  }
+};
+
+const OPTION_CHECKS: Record<keyof JobOptions, Check> = {
+  tsConfigPath: isString,
+  outputModulePath: isString,
+  customTypeMappings: isTypeMappings,
+  customHeader: isString,
+  customFooter: isString,
+  customTypeAnnotations: isStringArray,
+  strict: isBoolean,
+  lints: isLints,
+  templateLiteralNewtypes: isBoolean,
+  classMemberVisibility: isOneOf("public", "protected", "all"),
+  namespaces: isOneOf("modules", "flat"),
+  typeRenames: isStringRecord,
+  packageCrates: isStringRecord,
+  dryRun: isBoolean,
+  checkOnly: isBoolean,
+  verbose: isBoolean,
+  cacheDir: isString,
+  manifest: isBoolean,
+  sourceComments: isBoolean,
+  keepRegions: isBoolean,
+};
+
+const JOB_CHECKS: Record<keyof JobConfig, Check> = {
+  ...OPTION_CHECKS,
+  name: isString,
+  entryFile: isString,
+  typeNames: isStringArray,
+  outputPath: isString,
+  outputDir: isString,
+};
+
+/**
+ * Check a config, returning the problems found, each prefixed with the path of the offending value (`jobs[1].strict`)
+ */
+export function validateConfig(value: unknown): string[] {
+  const errors: string[] = [];
+  if (!isObject(value)) {
+    return ["the config must be an object with a `jobs` array"];
+  }
+
+  const { jobs, ...options } = value;
+  checkKeys(options, "", OPTION_CHECKS, errors);
+  if (!Array.isArray(jobs) || jobs.length === 0) {
+    errors.push("jobs must be a non-empty array");
+    return errors;
+  }
+
+  const outputs = new Map<string, string>();
+  const names = new Map<string, string>();
+  jobs.forEach((job: unknown, i) => {
+    const at = `jobs[${i}]`;
+    if (!isObject(job)) {
+      errors.push(`${at} must be an object`);
+      return;
+    }
+    checkKeys(job, at, JOB_CHECKS, errors);
+    if (job["entryFile"] === undefined) {
+      errors.push(`${at}.entryFile is required`);
+    }
+    const output = job["outputPath"] ?? job["outputDir"];
+    if (output === undefined) {
+      errors.push(`${at} needs an outputPath or an outputDir`);
+    } else if (job["outputPath"] !== undefined && job["outputDir"] !== undefined) {
+      errors.push(`${at} can't have both an outputPath and an outputDir`);
+    } else if (typeof output === "string") {
+      const other = outputs.get(path.normalize(output));
+      if (other) {
+        errors.push(`${at} writes to ${output}, like ${other}`);
+      }
+      outputs.set(path.normalize(output), at);
+    }
+    if (typeof job["name"] === "string") {
+      const other = names.get(job["name"]);
+      if (other) {
+        errors.push(`${at}.name "${job["name"]}" is also the name of ${other}`);
+      }
+      names.set(job["name"], at);
+    }
+  });
+  return errors;
+}
+
+function checkKeys(value: Record<string, unknown>, at: string, checks: Record<string, Check>, errors: string[]): void {
+  for (const [key, item] of Object.entries(value)) {
+    const keyAt = at ? `${at}.${key}` : key;
+    const check = checks[key];
+    if (!check) {
+      errors.push(`${keyAt} is not a known option`);
+    } else if (item !== undefined) {
+      check(item, keyAt, errors);
+    }
+  }
+}
+
+function isObject(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+const PATH_OPTIONS: string[] = ["entryFile", "tsConfigPath", "outputPath", "outputDir", "cacheDir"];
+
+function resolveConfigPaths(config: ProjectConfig, configDir: string): ProjectConfig {
+  const resolvePaths = <T extends object>(options: T): T =>
+    Object.fromEntries(
+      Object.entries(options).map(([key, value]) => [
+        key,
+        PATH_OPTIONS.includes(key) && typeof value === "string" ? path.resolve(configDir, value) : value,
+      ]),
+    ) as T;
+  return { ...resolvePaths(config), jobs: config.jobs.map(resolvePaths) };
+}
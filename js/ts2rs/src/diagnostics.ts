@@ -0,0 +1,180 @@
+/**
+ * Warnings and errors of conversions, with stable codes and source locations
+ */
+
+import * as fs from "node:fs";
+import * as path from "node:path";
+import type { Node } from "ts-morph";
+import type { ConversionOptions, Diagnostic, DiagnosticName, LintLevel, LintName } from "./types";
+
+/**
+ * Stable code of each kind of diagnostic, with the fix suggested by default
+ */
+export const DIAGNOSTIC_CODES: Record<DiagnosticName, { code: string; suggestion?: string }> = {
+  "value-fallback": {
+    code: "TS2RS001",
+    suggestion: "give the type a name, or map it to a Rust type with customTypeMappings",
+  },
+  "anonymous-struct": {
+    code: "TS2RS002",
+    suggestion: "declare the object type as a named interface or type alias",
+  },
+  "type-collision": {
+    code: "TS2RS003",
+    suggestion: "rename the types with typeRenames (e.g. \"src/billing.ts#Status\")",
+  },
+  "deferred-conditional": {
+    code: "TS2RS004",
+    suggestion: "declare aliases of the conditional type with concrete type arguments",
+  },
+  "unresolvable-union": {
+    code: "TS2RS005",
+    suggestion: "map the variants that can't be converted with customTypeMappings",
+  },
+  "no-types": {
+    code: "TS2RS006",
+    suggestion: "export the types to convert from the entry file, or list them in typeNames",
+  },
+  "untagged-union": {
+    code: "TS2RS007",
+    suggestion: "add a string discriminant property to the variants, e.g. `kind: \"circle\"`",
+  },
+  "dropped-methods": {
+    code: "TS2RS008",
+    suggestion: "implement the methods on the Rust type, or allow the lint with @ts2rs-allow dropped-methods",
+  },
+  "lossy-number": {
+    code: "TS2RS009",
+    suggestion: "use a named enum for the allowed values, or map the type with customTypeMappings",
+  },
+  "orphaned-region": {
+    code: "TS2RS010",
+    suggestion: "move the code next to the type that replaced it, or delete the region",
+  },
+  "unsupported-type": { code: "TS2RS101" },
+  "type-not-found": {
+    code: "TS2RS102",
+    suggestion: "check the name, and that the type is declared in or imported by the entry file",
+  },
+  "conflicting-declaration": {
+    code: "TS2RS103",
+    suggestion: "declare the property with the same type in every declaration of the interface",
+  },
+  "invalid-constant": {
+    code: "TS2RS104",
+    suggestion: "declare the constant with `const` and literal data, or don't list it in typeNames",
+  },
+  "missing-item": {
+    code: "TS2RS201",
+    suggestion: "declare the type in the Rust file, or don't list it in typeNames",
+  },
+  "missing-field": { code: "TS2RS202" },
+  "extra-field": { code: "TS2RS203" },
+  "optionality-mismatch": { code: "TS2RS204" },
+  "rename-mismatch": { code: "TS2RS205" },
+  "type-mismatch": { code: "TS2RS206" },
+  "variant-mismatch": { code: "TS2RS207" },
+};
+
+/**
+ * Level of each lint unless configured with `lints` (or `strict`)
+ */
+export const DEFAULT_LINT_LEVELS: Record<LintName, LintLevel> = {
+  "value-fallback": "warn",
+  "anonymous-struct": "warn",
+  "untagged-union": "allow",
+  "dropped-methods": "allow",
+  "lossy-number": "allow",
+  "type-collision": "warn",
+};
+
+export function isLintName(name: string): name is LintName {
+  return name in DEFAULT_LINT_LEVELS;
+}
+
+export function getLintLevel(name: LintName, options: ConversionOptions): LintLevel {
+  const strictLevel = options.strict && (name === "value-fallback" || name === "type-collision") ? "deny" : undefined;
+  return options.lints?.[name] ?? strictLevel ?? DEFAULT_LINT_LEVELS[name];
+}
+
+/**
+ * The lints listed by the `@ts2rs-allow` tags of JSDoc comments (`@ts2rs-allow value-fallback, untagged-union`)
+ */
+export function getAllowedLints(tags: { getTagName(): string; getCommentText(): string | undefined }[]): LintName[] {
+  return tags
+    .filter((tag) => tag.getTagName() === "ts2rs-allow")
+    .flatMap((tag) => (tag.getCommentText() ?? "").split(/[\s,]+/))
+    .filter(isLintName);
+}
+
+/**
+ * Where a diagnostic points to, its message aside
+ */
+export type DiagnosticDetails = Omit<Diagnostic, "code" | "name" | "severity" | "message">;
+
+export function createDiagnostic(
+  name: DiagnosticName,
+  severity: Diagnostic["severity"],
+  message: string,
+  details: DiagnosticDetails = {},
+): Diagnostic {
+  const { code, suggestion: defaultSuggestion } = DIAGNOSTIC_CODES[name];
+  const suggestion = details.suggestion ?? defaultSuggestion;
+  return { code, name, severity, message, ...details, ...(suggestion ? { suggestion } : {}) };
+}
+
+/**
+ * The file and the span (1-based lines and columns) of a node
+ */
+export function getNodeLocation(node: Node): Pick<Diagnostic, "file" | "line" | "column" | "endLine" | "endColumn"> {
+  const sourceFile = node.getSourceFile();
+  const start = sourceFile.getLineAndColumnAtPos(node.getStart());
+  const end = sourceFile.getLineAndColumnAtPos(node.getEnd());
+  return { file: sourceFile.getFilePath(), line: start.line, column: start.column, endLine: end.line, endColumn: end.column };
+}
+
+/**
+ * Format a diagnostic like rustc does: the message, its location, the source line underlined, then notes.
+ * Files are shown relative to the working directory when inside it.
+ */
+export function formatDiagnostic(diagnostic: Diagnostic): string {
+  const lines = [`${diagnostic.severity}[${diagnostic.code}]: ${diagnostic.message}`];
+  const notes: string[] = [];
+  if (diagnostic.typeName) {
+    notes.push(`= note: in ${[diagnostic.typeName, ...(diagnostic.fieldPath ? [diagnostic.fieldPath] : [])].join(".")}`);
+  }
+  if (diagnostic.suggestion) {
+    notes.push(`= help: ${diagnostic.suggestion}`);
+  }
+
+  if (!diagnostic.file) {
+    return [...lines, ...notes.map((note) => `  ${note}`)].join("\n");
+  }
+  const relative = path.relative(process.cwd(), diagnostic.file);
+  const file = relative.startsWith("..") || path.isAbsolute(relative) ? diagnostic.file : relative;
+  const sourceLine = diagnostic.line ? readLine(diagnostic.file, diagnostic.line) : undefined;
+  const gutter = " ".repeat(String(diagnostic.line ?? "").length);
+  lines.push(`${gutter}--> ${[file, diagnostic.line, diagnostic.column].filter((part) => part !== undefined).join(":")}`);
+  if (sourceLine !== undefined && diagnostic.column !== undefined) {
+    // Multi-line spans are underlined up to the end of their first line
+    const start = diagnostic.column - 1;
+    const end = diagnostic.endLine === diagnostic.line && diagnostic.endColumn !== undefined
+      ? diagnostic.endColumn - 1
+      : sourceLine.trimEnd().length;
+    lines.push(`${gutter} |`);
+    lines.push(`${diagnostic.line} | ${sourceLine}`);
+    lines.push(`${gutter} | ${" ".repeat(start)}${"^".repeat(Math.max(1, end - start))}`);
+  }
+  if (notes.length > 0) {
+    lines.push(`${gutter} |`, ...notes.map((note) => `${gutter} ${note}`));
+  }
+  return lines.join("\n");
+}
+
+function readLine(file: string, line: number): string | undefined {
+  try {
+    return fs.readFileSync(file, "utf-8").split(/\r?\n/)[line - 1];
+  } catch {
+    return undefined;
+  }
+}
@@ -0,0 +1,144 @@
+/**
+ * Comparison of generated code with existing files, for `checkOnly`
+ */
+
+/**
+ * Header lines that change without the generated code changing, compared by prefix only. The `// Source:` line
+ * isn't one: it is relative to the project, so it only changes when the output is generated from another file.
+ */
+const VOLATILE_HEADER_PREFIXES: string[] = ["// This file is auto-generated by ts2rs"];
+
+/**
+ * Lines of context around the changes of a unified diff
+ */
+const CONTEXT_LINES = 3;
+
+type Edit = { kind: " " | "-" | "+"; line: string; oldLine: number; newLine: number };
+
+/**
+ * A unified diff from the existing content of a file (undefined if missing) to the generated code, or undefined
+ * if they only differ by volatile header lines (see `VOLATILE_HEADER_PREFIXES`)
+ */
+export function diffGenerated(file: string, existing: string | undefined, generated: string): string | undefined {
+  const oldLines = existing === undefined ? [] : splitLines(existing);
+  const newLines = splitLines(generated);
+  const edits = diffLines(oldLines, newLines);
+  if (edits.every((edit) => edit.kind === " ")) {
+    return undefined;
+  }
+
+  const lines = [existing === undefined ? "--- /dev/null" : `--- ${file}`, `+++ ${file} (generated)`];
+  for (const hunk of getHunks(edits)) {
+    const oldCount = hunk.filter((edit) => edit.kind !== "+").length;
+    const newCount = hunk.filter((edit) => edit.kind !== "-").length;
+    const oldStart = oldCount === 0 ? hunk[0]!.oldLine : hunk[0]!.oldLine + 1;
+    const newStart = newCount === 0 ? hunk[0]!.newLine : hunk[0]!.newLine + 1;
+    lines.push(`@@ -${oldStart},${oldCount} +${newStart},${newCount} @@`);
+    lines.push(...hunk.map((edit) => edit.kind + edit.line));
+  }
+  return lines.join("\n") + "\n";
+}
+
+function splitLines(content: string): string[] {
+  const lines = content.split(/\r?\n/);
+  if (lines[lines.length - 1] === "") {
+    lines.pop();
+  }
+  return lines;
+}
+
+/**
+ * What a line is compared by: volatile lines of the header (the leading comment lines) by their prefix
+ */
+function getLineKeys(lines: string[]): string[] {
+  const headerEnd = lines.findIndex((line) => !line.startsWith("//"));
+  return lines.map((line, i) => {
+    const prefix = headerEnd === -1 || i < headerEnd
+      ? VOLATILE_HEADER_PREFIXES.find((p) => line.startsWith(p))
+      : undefined;
+    return prefix ?? line;
+  });
+}
+
+/**
+ * The shortest edit script turning `oldLines` into `newLines` (longest common subsequence of the lines between
+ * their common prefix and suffix). Unchanged lines keep their existing content.
+ */
+function diffLines(oldLines: string[], newLines: string[]): Edit[] {
+  const oldKeys = getLineKeys(oldLines);
+  const newKeys = getLineKeys(newLines);
+
+  let prefix = 0;
+  while (prefix < oldKeys.length && prefix < newKeys.length && oldKeys[prefix] === newKeys[prefix]) {
+    prefix++;
+  }
+  let suffix = 0;
+  while (
+    suffix < oldKeys.length - prefix &&
+    suffix < newKeys.length - prefix &&
+    oldKeys[oldKeys.length - 1 - suffix] === newKeys[newKeys.length - 1 - suffix]
+  ) {
+    suffix++;
+  }
+
+  const n = oldKeys.length - prefix - suffix;
+  const m = newKeys.length - prefix - suffix;
+  // lengths[i * (m + 1) + j]: length of the longest common subsequence of the old lines from i and the new lines from j
+  const lengths = new Uint32Array((n + 1) * (m + 1));
+  for (let i = n - 1; i >= 0; i--) {
+    for (let j = m - 1; j >= 0; j--) {
+      lengths[i * (m + 1) + j] = oldKeys[prefix + i] === newKeys[prefix + j]
+        ? lengths[(i + 1) * (m + 1) + j + 1]! + 1
+        : Math.max(lengths[(i + 1) * (m + 1) + j]!, lengths[i * (m + 1) + j + 1]!);
+    }
+  }
+
+  const edits: Edit[] = [];
+  const keep = (oldLine: number, newLine: number) =>
+    edits.push({ kind: " ", line: oldLines[oldLine]!, oldLine, newLine });
+  for (let k = 0; k < prefix; k++) {
+    keep(k, k);
+  }
+  let i = 0;
+  let j = 0;
+  while (i < n || j < m) {
+    if (i < n && j < m && oldKeys[prefix + i] === newKeys[prefix + j]) {
+      keep(prefix + i++, prefix + j++);
+    } else if (i < n && (j === m || lengths[(i + 1) * (m + 1) + j]! >= lengths[i * (m + 1) + j + 1]!)) {
+      edits.push({ kind: "-", line: oldLines[prefix + i]!, oldLine: prefix + i++, newLine: prefix + j });
+    } else {
+      edits.push({ kind: "+", line: newLines[prefix + j]!, oldLine: prefix + i, newLine: prefix + j++ });
+    }
+  }
+  for (let k = 0; k < suffix; k++) {
+    keep(oldLines.length - suffix + k, newLines.length - suffix + k);
+  }
+  return edits;
+}
+
+/**
+ * Group the changes with their surrounding context, merging changes whose contexts overlap
+ */
+function getHunks(edits: Edit[]): Edit[][] {
+  const hunks: Edit[][] = [];
+  let start = -1;
+  let end = -1;
+  edits.forEach((edit, i) => {
+    if (edit.kind === " ") {
+      return;
+    }
+    if (start !== -1 && i - CONTEXT_LINES <= end + CONTEXT_LINES) {
+      end = i;
+      return;
+    }
+    if (start !== -1) {
+      hunks.push(edits.slice(start, end + CONTEXT_LINES + 1));
+    }
+    start = Math.max(0, i - CONTEXT_LINES);
+    end = i;
+  });
+  if (start !== -1) {
+    hunks.push(edits.slice(start, end + CONTEXT_LINES + 1));
+  }
+  return hunks;
+}
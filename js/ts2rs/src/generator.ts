@@ -2,6 +2,7 @@
  * Rust code generator from resolved TypeScript types
  */
 
+import * as path from "node:path";
 import type {
   ResolvedType,
   CollectedType,
@@ -13,8 +14,70 @@ import type {
   StructField,
   TypeAliasType,
   CustomTypeMapping,
+  TemplateLiteralPart,
+  ConstantType,
+  ConstantValue,
+  ExternalTypeUsage,
+  Diagnostic,
+  LintName,
+  ManifestItem,
+  ManifestField,
+  ManifestFallback,
+  SourceLocation,
 } from "./types";
 import { TypeConversionError } from "./types";
+import { createDiagnostic, getLintLevel } from "./diagnostics";
+import { keepSlot } from "./keep";
+import { findTsConfig } from "./module-resolution";
+
+const RUST_KEYWORDS = new Set([
+  "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+  "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+  "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+  "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro", "override", "priv",
+  "try", "typeof", "unsized", "virtual", "yield",
+]);
+
+/**
+ * Helper emitted once when template literal newtypes are generated
+ */
+const TEMPLATE_LITERAL_MATCHER = [
+  "#[allow(dead_code)]",
+  "#[derive(Debug, Clone, Copy)]",
+  "enum TemplateLiteralPart {",
+  "    Text(&'static str),",
+  "    String,",
+  "    Number,",
+  "    BigInt,",
+  "}",
+  "",
+  "fn matches_template_literal(value: &str, parts: &[TemplateLiteralPart]) -> bool {",
+  "    let Some((first, rest)) = parts.split_first() else {",
+  "        return value.is_empty();",
+  "    };",
+  "    if let TemplateLiteralPart::Text(text) = first {",
+  "        return value",
+  "            .strip_prefix(text)",
+  "            .is_some_and(|remaining| matches_template_literal(remaining, rest));",
+  "    }",
+  "    (0..=value.len())",
+  "        .filter(|&end| value.is_char_boundary(end))",
+  "        .any(|end| {",
+  "            let hole = &value[..end];",
+  "            let matches_hole = match first {",
+  "                TemplateLiteralPart::Number => {",
+  "                    hole.trim() == hole && hole.parse::<f64>().is_ok_and(f64::is_finite)",
+  "                }",
+  "                TemplateLiteralPart::BigInt => {",
+  "                    let digits = hole.strip_prefix('-').unwrap_or(hole);",
+  "                    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())",
+  "                }",
+  "                _ => true,",
+  "            };",
+  "            matches_hole && matches_template_literal(&value[end..], rest)",
+  "        })",
+  "}",
+];
 
 /**
  * Generates Rust code from collected TypeScript types
@@ -22,7 +85,20 @@ import { TypeConversionError } from "./types";
 export class RustGenerator {
   private options: ConversionOptions;
   private generatedTypes: Set<string> = new Set();
-  private warnings: string[] = [];
+  private currentNamespace: string[] = []; // Namespace of the module being generated
+  private flatNames: Map<string, string> = new Map(); // Qualified name => Rust name in flat namespace mode
+  private modules: Map<string, string[]> = new Map(); // Type name => module of its source file, in output directory mode
+  private originalNames: Map<string, string> = new Map(); // Type name => name before disambiguation, in output directory mode
+  private currentModule: string[] = []; // Module of the source file being generated, in output directory mode
+  private moduleImports: Map<string, string> = new Map(); // Rust name => path imported into the current module
+  private localNames: Set<string> = new Set(); // Top-level Rust names declared in the current module
+  private currentType: string | undefined; // Collected type being generated
+  private currentCollected: CollectedType | undefined; // Collected type being generated, for diagnostics
+  private externalTypes: Map<string, ExternalTypeUsage> = new Map(); // Rust path => usage
+  private warnings: Diagnostic[] = [];
+  private errors: Diagnostic[] = []; // Denied lints, thrown together once everything is generated
+  private manifestItems: ManifestItem[] = [];
+  private describing = false; // Describing a generated type for the manifest, its lints were already reported
 
   constructor(options: ConversionOptions) {
     this.options = options;
@@ -56,27 +132,253 @@ export class RustGenerator {
     }
   }
 
+  /**
+   * Report a lint about the collected type being generated at its level, unless allowed by `@ts2rs-allow`
+   */
+  private lint(name: LintName, message: string): void {
+    if (this.describing) {
+      return;
+    }
+    const collected = this.currentCollected;
+    const level = collected?.allowedLints?.includes(name) ? "allow" : getLintLevel(name, this.options);
+    if (level === "allow") {
+      return;
+    }
+    const diagnostic = createDiagnostic(name, level === "deny" ? "error" : "warning", message, {
+      ...(collected ? { file: collected.sourceFile, typeName: collected.name } : {}),
+    });
+    (level === "deny" ? this.errors : this.warnings).push(diagnostic);
+  }
+
+  /**
+   * The `// from <file>:<line> (<Type>.<member>)` comment of the type being generated or of one of its members,
+   * when `sourceComments` is set
+   */
+  private sourceComment(source: Partial<SourceLocation> | undefined, ...members: string[]): string[] {
+    const collected = this.currentCollected;
+    if (!this.options.sourceComments || !source?.file || !collected) {
+      return [];
+    }
+    const location = [this.getSourcePath(source.file), ...(source.line !== undefined ? [source.line] : [])].join(":");
+    return [`// from ${location} (${[collected.originalName ?? collected.name, ...members].join(".")})`];
+  }
+
+  /**
+   * Comments giving why parts of a type fell back to `serde_json::Value`, when `sourceComments` is set
+   */
+  private fallbackComments(type: ResolvedType): string[] {
+    if (!this.options.sourceComments) {
+      return [];
+    }
+    const fallbacks: ManifestFallback[] = [];
+    this.collectFallbacks(type, [], fallbacks);
+    return fallbacks.map((fallback) => {
+      const reason = fallback.reason.replace(/\s+/g, " "); // Types in reasons may span several lines
+      return `// serde_json::Value${fallback.path ? ` in ${fallback.path}` : ""}: ${reason}`;
+    });
+  }
+
   /**
    * Generate Rust code from collected types
    */
   generate(collectedTypes: CollectedType[]): ConversionResult {
-    const lines: string[] = [];
+    const result = this.options.outputDir ? this.generateDirectory(collectedTypes) : this.generateSingleFile(collectedTypes);
+    if (this.errors.length > 0) {
+      throw TypeConversionError.fromDiagnostics(this.errors);
+    }
+    return result;
+  }
 
-    lines.push("// This file is auto-generated by ts2rs. Do not edit manually.");
-    lines.push("// Source: " + this.options.entryFile);
-    lines.push("");
+  private generateSingleFile(collectedTypes: CollectedType[]): ConversionResult {
+    const sortedTypes = this.sortByDependency(collectedTypes);
+    if (this.options.namespaces === "flat") {
+      this.assignFlatNames(sortedTypes);
+    }
+
+    return {
+      rustCode: this.generateFile(sortedTypes, this.options.entryFile).join("\n"),
+      convertedTypes: Array.from(this.generatedTypes),
+      warnings: this.warnings,
+      externalTypes: Array.from(this.externalTypes.values()),
+      rustPaths: this.getRustPaths(sortedTypes),
+      ...(this.options.manifest ? { manifest: { version: 1, items: this.manifestItems } } : {}),
+    };
+  }
+
+  /**
+   * Generate one module per TypeScript source file, declared by a `mod.rs` that re-exports their types.
+   * Types referenced from other modules are imported with `use crate::...::Type`.
+   */
+  private generateDirectory(collectedTypes: CollectedType[]): ConversionResult {
+    const sortedTypes = this.sortByDependency(collectedTypes);
+    const fileModules = this.getFileModules(sortedTypes.map((t) => t.sourceFile));
+    for (const t of sortedTypes) {
+      this.modules.set(t.name, fileModules.get(t.sourceFile)!);
+      if (t.originalName) {
+        this.originalNames.set(t.name, t.originalName);
+      }
+    }
+
+    const sourceFiles = [...fileModules.keys()].sort();
+    if (this.options.namespaces === "flat") {
+      for (const sourceFile of sourceFiles) {
+        this.assignFlatNames(sortedTypes.filter((t) => t.sourceFile === sourceFile));
+      }
+    }
+
+    const files: Record<string, string> = {};
+    const tree = new Map<string, Set<string>>([["", new Set()]]); // Module => child modules
+    for (const sourceFile of sourceFiles) {
+      const module = fileModules.get(sourceFile)!;
+      const types = sortedTypes.filter((t) => t.sourceFile === sourceFile);
+      this.currentModule = module;
+      this.moduleImports = new Map();
+      this.localNames = new Set(
+        types.filter((t) => this.getNamespace(t.name).length === 0).map((t) => this.toRustTypeName(t.name)),
+      );
+      files[module.join("/")] = this.generateFile(types, sourceFile).join("\n");
+
+      module.forEach((segment, i) => {
+        const parent = module.slice(0, i).join("/");
+        tree.set(parent, (tree.get(parent) ?? new Set()).add(segment));
+        if (!tree.has(module.slice(0, i + 1).join("/"))) {
+          tree.set(module.slice(0, i + 1).join("/"), new Set());
+        }
+      });
+    }
+    this.currentModule = [];
+
+    // Modules with child modules live in `<module>/mod.rs`, the others in `<module>.rs`
+    const output: Record<string, string> = {};
+    for (const [module, children] of tree) {
+      if (!module) {
+        output["mod.rs"] = this.generateRootModule(sortedTypes, [...children]).join("\n");
+        continue;
+      }
+      const lines = (files[module] ?? this.generateFileHeader().join("\n")).split("\n");
+      if (children.size > 0) {
+        while (lines[lines.length - 1] === "") {
+          lines.pop();
+        }
+        lines.push("", ...[...children].map((child) => `pub mod ${child};`), "");
+      }
+      const filePath = children.size > 0 ? `${module}/mod.rs` : `${module}.rs`;
+      output[filePath.replace(/(^|\/)r#/g, "$1")] = lines.join("\n");
+    }
+
+    return {
+      rustCode: output["mod.rs"]!,
+      convertedTypes: Array.from(this.generatedTypes),
+      warnings: this.warnings,
+      externalTypes: Array.from(this.externalTypes.values()),
+      rustPaths: this.getRustPaths(sortedTypes),
+      files: output,
+      ...(this.options.manifest ? { manifest: { version: 1, items: this.manifestItems } } : {}),
+    };
+  }
+
+  /**
+   * Rust paths of the generated types (not constants) by type key
+   */
+  private getRustPaths(types: CollectedType[]): Record<string, string> {
+    const paths: Record<string, string> = {};
+    for (const t of types) {
+      if (!t.key || t.type.kind === "constant" || !this.generatedTypes.has(t.name)) {
+        continue;
+      }
+      paths[t.key] = this.getRustPath(t);
+    }
+    return paths;
+  }
+
+  /**
+   * Full Rust path of a generated type or constant
+   */
+  private getRustPath(collected: CollectedType): string {
+    const namespace = this.options.namespaces === "flat" ? [] : this.getNamespace(collected.name);
+    return [
+      this.getOutputModulePath(),
+      ...(this.modules.get(collected.name) ?? []),
+      ...namespace.map((segment) => this.toRustFieldName(segment)),
+      collected.type.kind === "constant" ? this.toRustConstantName(collected.type.name) : this.toRustTypeName(collected.name),
+    ].join("::");
+  }
+
+  /**
+   * The root `mod.rs` re-exports the top-level types of all modules, except names declared in several modules
+   */
+  private generateRootModule(types: CollectedType[], children: string[]): string[] {
+    const exportsByName = new Map<string, string[]>();
+    for (const t of types) {
+      if (!this.generatedTypes.has(t.name) || this.getNamespace(t.name).length > 0) {
+        continue;
+      }
+      const name = t.type.kind === "constant" ? this.toRustConstantName(t.type.name) : this.toRustTypeName(t.name);
+      exportsByName.set(name, [...(exportsByName.get(name) ?? []), this.modules.get(t.name)!.join("::")]);
+    }
+
+    const exportsByModule = new Map<string, string[]>();
+    for (const [name, modules] of exportsByName) {
+      if (new Set(modules).size === 1) {
+        exportsByModule.set(modules[0]!, [...(exportsByModule.get(modules[0]!) ?? []), name]);
+      }
+    }
+
+    const lines = this.generateFileHeader(this.options.entryFile);
+    lines.push(...children.map((child) => `pub mod ${child};`), "");
+    for (const [module, names] of [...exportsByModule].sort(([a], [b]) => a.localeCompare(b))) {
+      lines.push(names.length === 1 ? `pub use ${module}::${names[0]};` : `pub use ${module}::{${names.join(", ")}};`);
+    }
+    if (exportsByModule.size > 0) {
+      lines.push("");
+    }
+    if (this.options.keepRegions) {
+      lines.push(...keepSlot(), "");
+    }
+    return lines;
+  }
+
+  private generateFileHeader(source?: string): string[] {
+    return [
+      "// This file is auto-generated by ts2rs. Do not edit manually.",
+      ...(source ? ["// Source: " + this.getSourcePath(source)] : []),
+      "",
+    ];
+  }
+
+  /**
+   * Path of a source file relative to the project (the directory of the tsconfig, or of the entry file without one),
+   * so the output is the same wherever the project is checked out
+   */
+  private getSourcePath(source: string): string {
+    const tsConfigPath = this.options.tsConfigPath ?? findTsConfig(this.options.entryFile);
+    const projectDir = path.dirname(tsConfigPath ?? this.options.entryFile);
+    return path.relative(projectDir, source).split(path.sep).join("/");
+  }
+
+  /**
+   * Generate a Rust file declaring the given types, with the imports and helpers they use
+   */
+  private generateFile(types: CollectedType[], source: string): string[] {
+    const lines = this.generateFileHeader(source);
 
     if (this.options.customHeader) {
       lines.push(this.options.customHeader);
       lines.push("");
     }
 
-    lines.push("use serde::{Deserialize, Serialize};");
+    // Generate the items first, references to other modules add imports
+    const items = this.options.namespaces === "flat" ? this.generateItems(types) : this.generateModule(types, []);
+
+    if (!this.options.outputDir || types.some((t) => t.type.kind !== "constant")) {
+      lines.push("use serde::{Deserialize, Serialize};");
+    }
 
-    const needsHashMap = this.checkNeedsHashMap(collectedTypes);
-    const needsHashSet = this.checkNeedsHashSet(collectedTypes);
-    const needsSerdeJson = this.checkNeedsSerdeJson(collectedTypes);
-    const needsDoubleOption = this.checkNeedsDoubleOption(collectedTypes);
+    const needsHashMap = this.checkNeedsHashMap(types);
+    const needsHashSet = this.checkNeedsHashSet(types);
+    const needsSerdeJson = this.checkNeedsSerdeJson(types);
+    const needsDoubleOption = this.checkNeedsDoubleOption(types);
+    const needsLazyLock = this.checkNeedsLazyLock(types);
 
     if (needsHashMap || needsHashSet) {
       const collections: string[] = [];
@@ -85,10 +387,18 @@ export class RustGenerator {
       lines.push(`use std::collections::{${collections.join(", ")}};`);
     }
 
+    if (needsLazyLock) {
+      lines.push("use std::sync::LazyLock;");
+    }
+
     if (needsSerdeJson) {
       lines.push("use serde_json::Value;");
     }
 
+    for (const path of [...this.moduleImports.values()].sort()) {
+      lines.push(`use ${path};`);
+    }
+
     lines.push("");
 
     if (needsDoubleOption) {
@@ -102,47 +412,277 @@ export class RustGenerator {
       lines.push("");
     }
 
-    const sortedTypes = this.sortByDependency(collectedTypes);
+    if (this.checkNeedsTemplateLiteralMatcher(types)) {
+      lines.push(...TEMPLATE_LITERAL_MATCHER);
+      lines.push("");
+    }
+
+    lines.push(...items);
+
+    if (this.options.customFooter) {
+      lines.push(this.options.customFooter);
+      lines.push("");
+    }
 
-    for (const collected of sortedTypes) {
+    if (this.options.keepRegions) {
+      lines.push(...keepSlot(), "");
+    }
+
+    return lines;
+  }
+
+  private generateItems(types: CollectedType[]): string[] {
+    const lines: string[] = [];
+
+    for (const collected of types) {
       if (this.generatedTypes.has(collected.name)) {
         continue;
       }
 
+      this.currentType = collected.name;
+      this.currentCollected = collected;
       const typeCode = this.generateType(collected);
       if (typeCode) {
+        lines.push(...this.sourceComment({ file: collected.sourceFile, line: collected.line }));
         lines.push(typeCode);
+        if (this.options.keepRegions) {
+          lines.push(...keepSlot(collected.originalName ?? collected.name));
+        }
         lines.push("");
         this.generatedTypes.add(collected.name);
+        if (this.options.manifest) {
+          this.manifestItems.push(this.describeType(collected));
+        }
       }
     }
 
-    if (this.options.customFooter) {
-      lines.push(this.options.customFooter);
+    return lines;
+  }
+
+  /**
+   * Generate the types of a namespace, followed by its nested namespaces as `pub mod` blocks.
+   * Types are collected under their namespace-qualified name (`Api.V1.User`).
+   */
+  private generateModule(types: CollectedType[], namespace: string[]): string[] {
+    const ownTypes = types.filter((t) => this.getNamespace(t.name).join(".") === namespace.join("."));
+    const childNamespaces = [
+      ...new Set(
+        types
+          .map((t) => this.getNamespace(t.name))
+          .filter((ns) => ns.length > namespace.length && namespace.every((segment, i) => ns[i] === segment))
+          .map((ns) => ns[namespace.length]!),
+      ),
+    ];
+
+    this.currentNamespace = namespace;
+    const lines = this.generateItems(ownTypes);
+
+    // Modules glob-import the root of the generated file for the serde derives, collections and helpers
+    if (namespace.length > 0 && /\b(Serialize|Deserialize|HashMap|HashSet|Value|LazyLock)\b/.test(lines.join("\n"))) {
+      lines.unshift(`use ${"super::".repeat(namespace.length)}*;`, "");
+    }
+
+    for (const child of childNamespaces) {
+      const body = this.generateModule(types, [...namespace, child]);
+      while (body[body.length - 1] === "") {
+        body.pop();
+      }
+      lines.push(`pub mod ${this.toRustFieldName(child)} {`);
+      lines.push(...body.map((l) => (l ? "    " + l : l)));
+      lines.push("}");
       lines.push("");
     }
 
-    return {
-      rustCode: lines.join("\n"),
-      convertedTypes: Array.from(this.generatedTypes),
-      warnings: this.warnings,
+    this.currentNamespace = namespace;
+    return lines;
+  }
+
+  private getNamespace(name: string): string[] {
+    return this.getLocalName(name).split(".").slice(0, -1);
+  }
+
+  /**
+   * Types disambiguated from same-named types in other files keep their original name in output directory mode,
+   * where each file is a separate module
+   */
+  private getLocalName(name: string): string {
+    return this.originalNames.get(name) ?? name;
+  }
+
+  /**
+   * Map source files to module paths: their path relative to the common directory of the (non node_modules)
+   * source files, or within `node_modules` (`src/billing/status.ts` => `["billing", "status"]`), suffixed when two
+   * files would share a module
+   */
+  private getFileModules(sourceFiles: string[]): Map<string, string[]> {
+    const files = [...new Set(sourceFiles)];
+    const nodeModulesPath = (file: string) => {
+      const index = file.replace(/\\/g, "/").lastIndexOf("/node_modules/");
+      return index === -1 ? undefined : file.replace(/\\/g, "/").slice(index + "/node_modules/".length);
     };
+
+    const localDirs = files
+      .filter((file) => nodeModulesPath(file) === undefined)
+      .map((file) => file.replace(/\\/g, "/").split("/").slice(0, -1));
+    let common = localDirs[0]?.length ?? 0;
+    for (const dir of localDirs) {
+      while (common > 0 && dir.slice(0, common).join("/") !== localDirs[0]!.slice(0, common).join("/")) {
+        common--;
+      }
+    }
+
+    const modules = new Map<string, string[]>();
+    const takenModules = new Set<string>();
+    for (const file of files.sort()) {
+      const relative = nodeModulesPath(file) ?? file.replace(/\\/g, "/").split("/").slice(common).join("/");
+      const segments = relative
+        .replace(/(\.d)?\.[cm]?tsx?$/, "")
+        .split("/")
+        .filter((s) => s)
+        .map((segment) => this.toRustFieldName(segment.replace(/^@/, "")))
+        // `mod.rs` is the file of the parent module, so a `mod` module would overwrite it
+        .map((segment) => (segment === "r#mod" ? "mod_" : segment));
+
+      // Files whose names are the same module once sanitized (`user-profile.ts`, `user_profile.ts`) are suffixed
+      let module = segments;
+      for (let n = 2; takenModules.has(module.join("/")); n++) {
+        module = [...segments.slice(0, -1), `${segments.at(-1)}${n}`];
+      }
+      takenModules.add(module.join("/"));
+      modules.set(file, module);
+    }
+    return modules;
+  }
+
+  /**
+   * Rust path of the output's module: `outputModulePath`, or derived from the location of the output directory
+   * or file under `src` (`src/generated` => `crate::generated`, `src/api/types.rs` => `crate::api::types`)
+   */
+  private getOutputModulePath(): string {
+    if (this.options.outputModulePath) {
+      return this.options.outputModulePath;
+    }
+    const output = this.options.outputDir ?? this.options.outputPath?.replace(/\.rs$/, "");
+    if (!output) {
+      return "crate";
+    }
+    const segments = output.replace(/\\/g, "/").split("/").filter((s) => s);
+    const last = segments.at(-1);
+    if (!this.options.outputDir && (last === "mod" || (segments.at(-2) === "src" && (last === "lib" || last === "main")))) {
+      segments.pop(); // `src/api/mod.rs` => `crate::api`, `src/lib.rs` => `crate`
+    }
+    const src = segments.lastIndexOf("src");
+    const modules = src === -1 ? segments.slice(-1) : segments.slice(src + 1);
+    return ["crate", ...modules.map((segment) => this.toRustFieldName(segment))].join("::");
+  }
+
+  /**
+   * Name namespace members by prefixing the namespaces (`Api.User` => `ApiUser`), adding a numeric suffix
+   * if that name is already taken
+   */
+  private assignFlatNames(types: CollectedType[]): void {
+    const names = types.map((t) => this.getLocalName(t.name));
+    const taken = new Set(names.filter((name) => !name.includes(".")));
+    for (const [i, t] of types.entries()) {
+      if (!names[i]!.includes(".")) {
+        continue;
+      }
+      const base = names[i]!.split(".").map((s) => s.charAt(0).toUpperCase() + s.slice(1)).join("");
+      let candidate = base;
+      for (let i = 2; taken.has(candidate); i++) {
+        candidate = `${base}${i}`;
+      }
+      taken.add(candidate);
+      this.flatNames.set(t.name, candidate);
+    }
+  }
+
+  /**
+   * The name a collected type is declared with
+   */
+  private toRustTypeName(name: string): string {
+    if (this.options.namespaces === "flat") {
+      return this.flatNames.get(name) ?? this.getLocalName(name).split(".").join("");
+    }
+    return this.getLocalName(name).split(".").pop()!;
+  }
+
+  /**
+   * The path to a collected type from the module currently being generated
+   * (`super::Team` from `api`, `api::User` from the root)
+   */
+  private toRustTypePath(name: string): string {
+    const module = this.modules.get(name);
+    if (module && module.join("::") !== this.currentModule.join("::")) {
+      return this.toCrateTypePath(name, module);
+    }
+
+    if (this.options.namespaces === "flat") {
+      return this.toRustTypeName(name);
+    }
+
+    const namespace = this.getNamespace(name);
+    let common = 0;
+    while (
+      common < namespace.length &&
+      common < this.currentNamespace.length &&
+      namespace[common] === this.currentNamespace[common]
+    ) {
+      common++;
+    }
+
+    return [
+      ...Array<string>(this.currentNamespace.length - common).fill("super"),
+      ...namespace.slice(common).map((segment) => this.toRustFieldName(segment)),
+      this.toRustTypeName(name),
+    ].join("::");
+  }
+
+  /**
+   * The path to a type declared in another module in output directory mode. Types used at the top level of
+   * a module are imported, unless their name is already taken there.
+   */
+  private toCrateTypePath(name: string, module: string[]): string {
+    const rustName = this.toRustTypeName(name);
+    const namespace = this.options.namespaces === "flat" ? [] : this.getNamespace(name);
+    const path = [
+      this.getOutputModulePath(),
+      ...module,
+      ...namespace.map((segment) => this.toRustFieldName(segment)),
+      rustName,
+    ].join("::");
+
+    const imported = this.moduleImports.get(rustName);
+    if (this.currentNamespace.length > 0 || this.localNames.has(rustName) || (imported && imported !== path)) {
+      return path;
+    }
+    this.moduleImports.set(rustName, path);
+    return rustName;
   }
 
   private checkNeedsHashMap(types: CollectedType[]): boolean {
-    return types.some((t) => this.typeUsesHashMap(t.type));
+    return types.some((t) => t.type.kind !== "constant" && this.typeUsesHashMap(t.type));
   }
 
   private checkNeedsHashSet(types: CollectedType[]): boolean {
-    return types.some((t) => this.typeUsesHashSet(t.type));
+    return types.some((t) => t.type.kind !== "constant" && this.typeUsesHashSet(t.type));
   }
 
   private checkNeedsSerdeJson(types: CollectedType[]): boolean {
-    return types.some((t) => this.typeUsesSerdeJson(t.type));
+    return types.some((t) => t.type.kind === "constant" ? !this.constantRustType(t.type.value) : this.typeUsesSerdeJson(t.type));
   }
 
   private checkNeedsDoubleOption(types: CollectedType[]): boolean {
-    return types.some((t) => this.typeUsesDoubleOption(t.type));
+    return types.some((t) => t.type.kind !== "constant" && this.typeUsesDoubleOption(t.type));
+  }
+
+  private checkNeedsLazyLock(types: CollectedType[]): boolean {
+    return types.some((t) => t.type.kind === "constant" && !this.constantRustType(t.type.value));
+  }
+
+  private checkNeedsTemplateLiteralMatcher(types: CollectedType[]): boolean {
+    return !!this.options.templateLiteralNewtypes &&
+      types.some((t) => t.type.kind === "type_alias" && t.type.templatePatterns);
   }
 
   private typeUsesHashMap(type: ResolvedType): boolean {
@@ -161,6 +701,8 @@ export class RustGenerator {
         return type.elements.some((e) => this.typeUsesHashMap(e));
       case "union":
         return type.variants.some((v) => v.type && this.typeUsesHashMap(v.type));
+      case "external":
+        return type.typeArguments?.some((a) => this.typeUsesHashMap(a)) ?? false;
       case "type_parameter":
         return false;
       default:
@@ -183,6 +725,8 @@ export class RustGenerator {
         return type.elements.some((e) => this.typeUsesHashSet(e));
       case "union":
         return type.variants.some((v) => v.type && this.typeUsesHashSet(v.type));
+      case "external":
+        return type.typeArguments?.some((a) => this.typeUsesHashSet(a)) ?? false;
       case "type_parameter":
         return false;
       default:
@@ -207,6 +751,8 @@ export class RustGenerator {
         return type.elements.some((e) => this.typeUsesSerdeJson(e));
       case "union":
         return type.variants.some((v) => v.type && this.typeUsesSerdeJson(v.type));
+      case "external":
+        return type.typeArguments?.some((a) => this.typeUsesSerdeJson(a)) ?? false;
       case "record":
       case "map":
         return this.typeUsesSerdeJson(type.valueType);
@@ -257,7 +803,7 @@ export class RustGenerator {
       const type = typeMap.get(name);
       if (!type) return;
 
-      const deps = this.getTypeDependencies(type.type);
+      const deps = type.type.kind === "constant" ? [] : this.getTypeDependencies(type.type);
       for (const dep of deps) {
         if (typeMap.has(dep)) {
           visit(dep);
@@ -316,6 +862,104 @@ export class RustGenerator {
     return deps;
   }
 
+  /**
+   * The manifest entry of a generated type, with the Rust types as written in the module being generated
+   */
+  private describeType(collected: CollectedType): ManifestItem {
+    const { type } = collected;
+    this.describing = true;
+    try {
+      const item: ManifestItem = {
+        tsName: collected.originalName ?? collected.name,
+        sourceFile: this.getSourcePath(collected.sourceFile),
+        ...(collected.line !== undefined ? { line: collected.line } : {}),
+        rustPath: this.getRustPath(collected),
+        kind: type.kind,
+      };
+      const fallbacks: ManifestFallback[] = [];
+      switch (type.kind) {
+        case "struct":
+          item.fields = type.fields.map((field) => this.describeField(field));
+          type.fields.forEach((field) => this.collectFallbacks(field.type, [field.name], fallbacks));
+          break;
+        case "enum":
+          item.variants = type.variants.map((variant) => ({ rustName: variant.name, value: variant.value ?? variant.name }));
+          break;
+        case "union":
+          item.variants = type.variants.map((variant) => {
+            const value = type.discriminator && variant.discriminatorValue ? { value: variant.discriminatorValue } : {};
+            if (variant.type === null) {
+              return { rustName: variant.name, ...value };
+            }
+            this.collectFallbacks(variant.type, [variant.name], fallbacks);
+            return variant.type.kind === "struct" && variant.type.fields.length > 0
+              ? { rustName: variant.name, ...value, fields: variant.type.fields.map((field) => this.describeField(field)) }
+              : { rustName: variant.name, ...value, rustType: this.resolvedTypeToRust(variant.type) };
+          });
+          break;
+        case "type_alias":
+          item.rustType = this.resolvedTypeToRust(type.aliasedType);
+          this.collectFallbacks(type.aliasedType, [], fallbacks);
+          break;
+        case "constant":
+          item.rustType = this.constantRustType(type.value) ?? "LazyLock<Value>";
+          break;
+      }
+      if (fallbacks.length > 0) {
+        item.fallbacks = fallbacks;
+      }
+      return item;
+    } finally {
+      this.describing = false;
+    }
+  }
+
+  private describeField(field: StructField): ManifestField {
+    return {
+      tsName: field.name,
+      rustName: this.toRustFieldName(field.name),
+      rustType: this.resolvedTypeToRust(field.type),
+      optional: field.optional,
+    };
+  }
+
+  /**
+   * The types that fell back to `serde_json::Value` in a type, outside of the named types it references
+   */
+  private collectFallbacks(type: ResolvedType, fieldPath: string[], fallbacks: ManifestFallback[]): void {
+    switch (type.kind) {
+      case "json_value":
+        if (type.fallback) {
+          fallbacks.push({ ...(fieldPath.length > 0 ? { path: fieldPath.join(".") } : {}), reason: type.fallback });
+        }
+        break;
+      case "array":
+      case "set":
+        this.collectFallbacks(type.elementType, fieldPath, fallbacks);
+        break;
+      case "option":
+      case "box":
+        this.collectFallbacks(type.innerType, fieldPath, fallbacks);
+        break;
+      case "record":
+      case "map":
+        this.collectFallbacks(type.valueType, fieldPath, fallbacks);
+        break;
+      case "tuple":
+        type.elements.forEach((element) => this.collectFallbacks(element, fieldPath, fallbacks));
+        break;
+      case "struct":
+        if (!type.name) {
+          type.fields.forEach((field) => this.collectFallbacks(field.type, [...fieldPath, field.name], fallbacks));
+        }
+        type.typeArguments?.forEach((argument) => this.collectFallbacks(argument, fieldPath, fallbacks));
+        break;
+      case "external":
+        type.typeArguments?.forEach((argument) => this.collectFallbacks(argument, fieldPath, fallbacks));
+        break;
+    }
+  }
+
   private generateType(collected: CollectedType): string {
     const { type } = collected;
 
@@ -328,6 +972,8 @@ export class RustGenerator {
         return this.generateUnion(type);
       case "type_alias":
         return this.generateTypeAlias(type);
+      case "constant":
+        return this.generateConstant(type);
       default: {
         const exhaustiveCheck: never = type;
         throw new TypeConversionError(
@@ -350,36 +996,59 @@ export class RustGenerator {
     lines.push("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]");
     lines.push('#[serde(rename_all = "camelCase")]');
 
+    const name = this.toRustTypeName(type.name);
     const typeParams = type.typeParameters ? `<${type.typeParameters.join(", ")}>` : "";
-    lines.push(`pub struct ${type.name}${typeParams} {`);
+    lines.push(`pub struct ${name}${typeParams} {`);
 
+    // Generic parameters can't be inferred from the path of a default function, so they are given explicitly
+    const defaultsPath = type.typeParameters ? `${name}::<${type.typeParameters.join(", ")}>` : name;
     for (const field of type.fields) {
-      const fieldLines = this.generateStructField(field);
+      const fieldLines = this.generateStructField(field, defaultsPath);
       lines.push(...fieldLines.map((l) => "    " + l));
     }
 
     lines.push("}");
 
+    const defaultFunctions = this.generateDefaultFunctions(type.fields);
+    if (defaultFunctions.length > 0) {
+      lines.push("");
+      lines.push(`impl${typeParams} ${name}${typeParams} {`);
+      lines.push(...defaultFunctions.map((l) => (l ? "    " + l : l)));
+      lines.push("}");
+    }
+
     return lines.join("\n");
   }
 
-  private generateStructField(field: StructField): string[] {
-    const lines: string[] = [];
+  private generateStructField(field: StructField, defaultsPath: string): string[] {
+    const lines: string[] = this.sourceComment(field.source, field.name);
 
     if (field.documentation) {
       lines.push(this.formatDocComment(field.documentation));
     }
 
+    const isDoubleOption = field.optional &&
+      field.type.kind === "option" &&
+      field.type.innerType.kind === "option";
+
     if (
       field.optional &&
       field.type.kind === "option"
     ) {
-      if (field.type.innerType.kind === "option") {
+      if (isDoubleOption) {
         lines.push('#[serde(default, deserialize_with = "deserialize_optional_nullable")]');
       }
       lines.push('#[serde(skip_serializing_if = "Option::is_none")]');
     }
 
+    if (field.defaultValue && !isDoubleOption) {
+      if (field.defaultValue.kind === "default") {
+        lines.push("#[serde(default)]");
+      } else {
+        lines.push(`#[serde(default = "${defaultsPath}::${this.defaultFunctionName(field)}")]`);
+      }
+    }
+
     // Add custom field annotations from type mappings
     const fieldTypeName = this.getFieldTypeName(field.type);
     if (fieldTypeName) {
@@ -391,18 +1060,69 @@ export class RustGenerator {
       }
     }
 
-    const rustFieldName = this.toSnakeCase(field.name);
+    const rustFieldName = this.toRustFieldName(field.name);
 
-    if (rustFieldName !== field.name) {
+    if (field.exactName || rustFieldName !== field.name) {
       lines.push(`#[serde(rename = "${field.name}")]`);
     }
 
     const rustType = this.resolvedTypeToRust(field.type);
+    lines.push(...this.fallbackComments(field.type));
     lines.push(`pub ${rustFieldName}: ${rustType},`);
 
     return lines;
   }
 
+  /**
+   * Generate the functions providing literal serde defaults (from class property initializers)
+   */
+  private generateDefaultFunctions(fields: StructField[]): string[] {
+    const lines: string[] = [];
+
+    for (const field of fields) {
+      if (field.defaultValue?.kind !== "literal") {
+        continue;
+      }
+      if (field.optional && field.type.kind === "option" && field.type.innerType.kind === "option") {
+        continue;
+      }
+
+      let value = this.literalToRust(field.defaultValue.value);
+      if (field.type.kind === "option") {
+        value = `Some(${value})`;
+      }
+
+      if (lines.length > 0) {
+        lines.push("");
+      }
+      lines.push(`fn ${this.defaultFunctionName(field)}() -> ${this.resolvedTypeToRust(field.type)} {`);
+      lines.push(`    ${value}`);
+      lines.push("}");
+    }
+
+    return lines;
+  }
+
+  private defaultFunctionName(field: StructField): string {
+    return `default_${this.toRustFieldName(field.name).replace(/^r#/, "")}`;
+  }
+
+  private literalToRust(value: string | number | boolean): string {
+    if (typeof value === "string") {
+      return `${this.toRustStringLiteral(value)}.to_string()`;
+    }
+    if (typeof value === "number") {
+      const text = String(value);
+      return /[.e]/.test(text) ? text : `${text}.0`;
+    }
+    return `${value}`;
+  }
+
+  private toRustStringLiteral(value: string): string {
+    // JSON string escapes are valid Rust escapes, except `\uXXXX` which is `\u{XXXX}` in Rust
+    return JSON.stringify(value).replace(/\\u([0-9a-f]{4})/g, "\\u{$1}");
+  }
+
   /**
    * Extract the TypeScript type name from a resolved type for looking up custom mappings
    */
@@ -435,9 +1155,10 @@ export class RustGenerator {
     // support automatic numeric serialization without the serde_repr crate.
     // Instead, we serialize all enums as strings for consistency.
 
-    lines.push(`pub enum ${type.name} {`);
+    lines.push(`pub enum ${this.toRustTypeName(type.name)} {`);
 
     for (const variant of type.variants) {
+      lines.push(...this.sourceComment(variant.source, variant.name).map((l) => "    " + l));
       if (variant.documentation) {
         lines.push("    " + this.formatDocComment(variant.documentation));
       }
@@ -478,9 +1199,10 @@ export class RustGenerator {
       lines.push("#[serde(untagged)]");
     }
 
-    lines.push(`pub enum ${type.name} {`);
+    lines.push(`pub enum ${this.toRustTypeName(type.name)} {`);
 
     for (const variant of type.variants) {
+      lines.push(...this.sourceComment(variant.source, variant.name).map((l) => "    " + l));
       if (variant.documentation) {
         lines.push("    " + this.formatDocComment(variant.documentation));
       }
@@ -495,12 +1217,13 @@ export class RustGenerator {
         // Inline struct variant
         lines.push(`    ${variant.name} {`);
         for (const field of variant.type.fields) {
-          const fieldLines = this.generateUnionVariantField(field);
+          const fieldLines = this.generateUnionVariantField(field, variant.name);
           lines.push(...fieldLines.map((l) => "        " + l));
         }
         lines.push("    },");
       } else {
         const rustType = this.resolvedTypeToRust(variant.type);
+        lines.push(...this.fallbackComments(variant.type).map((l) => "    " + l));
         lines.push(`    ${variant.name}(${rustType}),`);
       }
     }
@@ -511,6 +1234,10 @@ export class RustGenerator {
   }
 
   private generateTypeAlias(type: TypeAliasType): string {
+    if (type.templatePatterns && this.options.templateLiteralNewtypes) {
+      return this.generateTemplateLiteralNewtype(this.toRustTypeName(type.name), type.templatePatterns, type.documentation);
+    }
+
     const lines: string[] = [];
 
     if (type.documentation) {
@@ -518,14 +1245,150 @@ export class RustGenerator {
     }
 
     const rustType = this.resolvedTypeToRust(type.aliasedType);
-    lines.push(`pub type ${type.name} = ${rustType};`);
+    lines.push(...this.fallbackComments(type.aliasedType));
+    lines.push(`pub type ${this.toRustTypeName(type.name)} = ${rustType};`);
+
+    return lines.join("\n");
+  }
+
+  /**
+   * Generate a `String` newtype that checks the template literal patterns when deserialized
+   */
+  private generateTemplateLiteralNewtype(
+    name: string,
+    patterns: TemplateLiteralPart[][],
+    documentation?: string,
+  ): string {
+    const lines: string[] = [];
+
+    if (documentation) {
+      lines.push(this.formatDocComment(documentation));
+    }
+
+    this.addCustomTypeAnnotations(lines);
+    lines.push("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]");
+    lines.push('#[serde(try_from = "String", into = "String")]');
+    lines.push(`pub struct ${name}(String);`);
+    lines.push("");
+    lines.push(`impl TryFrom<String> for ${name} {`);
+    lines.push("    type Error = String;");
+    lines.push("");
+    lines.push("    fn try_from(value: String) -> Result<Self, Self::Error> {");
+    lines.push("        const PATTERNS: &[&[TemplateLiteralPart]] = &[");
+    for (const parts of patterns) {
+      lines.push(`            &[${parts.map((p) => this.templateLiteralPartToRust(p)).join(", ")}],`);
+    }
+    lines.push("        ];");
+    lines.push("        if PATTERNS.iter().any(|parts| matches_template_literal(&value, parts)) {");
+    lines.push("            Ok(Self(value))");
+    lines.push("        } else {");
+    lines.push(`            Err(format!("\\"{value}\\" does not match the template literal type ${name}"))`);
+    lines.push("        }");
+    lines.push("    }");
+    lines.push("}");
+    lines.push("");
+    lines.push(`impl From<${name}> for String {`);
+    lines.push(`    fn from(value: ${name}) -> Self {`);
+    lines.push("        value.0");
+    lines.push("    }");
+    lines.push("}");
 
     return lines.join("\n");
   }
 
-  private generateUnionVariantField(field: StructField): string[] {
+  /**
+   * Generate a `pub const` for scalars and uniform arrays, or a lazily built `Value` for nested data
+   */
+  private generateConstant(type: ConstantType): string {
     const lines: string[] = [];
 
+    if (type.documentation) {
+      lines.push(this.formatDocComment(type.documentation));
+    }
+
+    const name = this.toRustConstantName(type.name);
+    const rustType = this.constantRustType(type.value);
+    if (rustType) {
+      lines.push(`pub const ${name}: ${rustType} = ${this.constantToRust(type.value)};`);
+    } else {
+      lines.push(`pub static ${name}: LazyLock<Value> = LazyLock::new(|| serde_json::json!(${this.constantToJson(type.value, "")}));`);
+    }
+
+    return lines.join("\n");
+  }
+
+  /**
+   * The Rust type of a constant, or undefined if it needs to be a `Value` (objects, `null`, mixed or empty arrays)
+   */
+  private constantRustType(value: ConstantValue): string | undefined {
+    switch (value.kind) {
+      case "literal":
+        if (typeof value.value === "string") return "&str";
+        if (typeof value.value === "number") return "f64";
+        return "bool";
+      case "array": {
+        const elementTypes = new Set(value.elements.map((e) => this.constantRustType(e)));
+        const [elementType] = elementTypes;
+        if (elementTypes.size !== 1 || !elementType) {
+          return undefined;
+        }
+        return `&[${elementType}]`;
+      }
+      default:
+        return undefined;
+    }
+  }
+
+  private constantToRust(value: ConstantValue): string {
+    switch (value.kind) {
+      case "literal":
+        return typeof value.value === "string" ? this.toRustStringLiteral(value.value) : this.literalToRust(value.value);
+      case "array":
+        return `&[${value.elements.map((e) => this.constantToRust(e)).join(", ")}]`;
+      default:
+        throw new TypeConversionError("constant", `Cannot generate a Rust constant from ${value.kind}`);
+    }
+  }
+
+  private constantToJson(value: ConstantValue, indent: string): string {
+    switch (value.kind) {
+      case "literal":
+        return typeof value.value === "string" ? this.toRustStringLiteral(value.value) : String(value.value);
+      case "null":
+        return "null";
+      case "array":
+        if (value.elements.every((e) => e.kind === "literal" || e.kind === "null")) {
+          return `[${value.elements.map((e) => this.constantToJson(e, indent)).join(", ")}]`;
+        }
+        return `[\n${value.elements
+          .map((e) => `${indent}    ${this.constantToJson(e, indent + "    ")}`)
+          .join(",\n")}\n${indent}]`;
+      case "object":
+        if (value.entries.length === 0) {
+          return "{}";
+        }
+        return `{\n${value.entries
+          .map(([key, e]) => `${indent}    ${this.toRustStringLiteral(key)}: ${this.constantToJson(e, indent + "    ")}`)
+          .join(",\n")}\n${indent}}`;
+    }
+  }
+
+  private templateLiteralPartToRust(part: TemplateLiteralPart): string {
+    switch (part.kind) {
+      case "text":
+        return `TemplateLiteralPart::Text(${this.toRustStringLiteral(part.value)})`;
+      case "string":
+        return "TemplateLiteralPart::String";
+      case "number":
+        return "TemplateLiteralPart::Number";
+      case "bigint":
+        return "TemplateLiteralPart::BigInt";
+    }
+  }
+
+  private generateUnionVariantField(field: StructField, variantName: string): string[] {
+    const lines: string[] = this.sourceComment(field.source, variantName, field.name);
+
     if (field.documentation) {
       lines.push(this.formatDocComment(field.documentation));
     }
@@ -540,13 +1403,14 @@ export class RustGenerator {
       lines.push('#[serde(skip_serializing_if = "Option::is_none")]');
     }
 
-    const rustFieldName = this.toSnakeCase(field.name);
+    const rustFieldName = this.toRustFieldName(field.name);
 
-    if (rustFieldName !== field.name) {
+    if (field.exactName || rustFieldName !== field.name) {
       lines.push(`#[serde(rename = "${field.name}")]`);
     }
 
     const rustType = this.resolvedTypeToRust(field.type);
+    lines.push(...this.fallbackComments(field.type));
     // Note: No 'pub' keyword for enum variant fields
     lines.push(`${rustFieldName}: ${rustType},`);
 
@@ -591,19 +1455,19 @@ export class RustGenerator {
         if (type.name) {
           if (type.typeArguments && type.typeArguments.length > 0) {
             const args = type.typeArguments.map((a) => this.resolvedTypeToRust(a)).join(", ");
-            return `${type.name}<${args}>`;
+            return `${this.toRustTypePath(type.name)}<${args}>`;
           }
-          return type.name;
+          return this.toRustTypePath(type.name);
         }
         // Anonymous struct - should not happen at top level
-        this.warnings.push("Anonymous struct encountered - using serde_json::Value");
+        this.lint("anonymous-struct", "Anonymous struct encountered - using serde_json::Value");
         return "Value";
 
       case "enum":
-        return type.name;
+        return this.toRustTypePath(type.name);
 
       case "union":
-        return type.name;
+        return this.toRustTypePath(type.name);
 
       case "literal":
         if (typeof type.value === "string") return "String";
@@ -617,6 +1481,25 @@ export class RustGenerator {
       case "type_parameter":
         return type.name;
 
+      case "external": {
+        const namespace = type.name.split(".");
+        const rustPath = [
+          ...(type.rustModule ? [type.rustModule] : []), // Types replacing excluded ones have a full path
+          ...namespace.slice(0, -1).map((segment) => this.toRustFieldName(segment)),
+          namespace[namespace.length - 1],
+        ].join("::");
+        const usage = this.externalTypes.get(rustPath) ??
+          { rustPath, ...(type.package ? { package: type.package } : {}), usedBy: [] };
+        if (this.currentType && !usage.usedBy.includes(this.currentType)) {
+          usage.usedBy.push(this.currentType);
+        }
+        this.externalTypes.set(rustPath, usage);
+        if (type.typeArguments && type.typeArguments.length > 0) {
+          return `${rustPath}<${type.typeArguments.map((a) => this.resolvedTypeToRust(a)).join(", ")}>`;
+        }
+        return rustPath;
+      }
+
       default:
         throw new TypeConversionError(
           "unknown",
@@ -641,12 +1524,43 @@ export class RustGenerator {
     }
   }
 
+  /**
+   * Convert a TypeScript property name to a valid Rust field identifier
+   */
+  private toRustFieldName(name: string): string {
+    // Segments are snake-cased on their own, so `de-DE` becomes `de_de` rather than `de__d_e`
+    let ident = name
+      .split(/[^A-Za-z0-9_]+/)
+      .filter((segment) => segment)
+      .map((segment) => (/^[A-Z0-9]+$/.test(segment) ? segment.toLowerCase() : this.toSnakeCase(segment)))
+      .join("_");
+    if (ident === "" || /^[0-9]/.test(ident)) {
+      ident = `_${ident}`;
+    }
+    if (RUST_KEYWORDS.has(ident)) {
+      // `self`, `super`, `crate` and `Self` cannot be raw identifiers
+      return ["self", "super", "crate"].includes(ident) ? `${ident}_` : `r#${ident}`;
+    }
+    return ident;
+  }
+
+  /**
+   * Convert a TypeScript constant name to SCREAMING_SNAKE_CASE (`maxItems` => `MAX_ITEMS`, `LIMITS` stays)
+   */
+  private toRustConstantName(name: string): string {
+    const ident = name
+      .replace(/([a-z0-9])([A-Z])/g, "$1_$2")
+      .replace(/([A-Z]+)([A-Z][a-z])/g, "$1_$2")
+      .replace(/[^A-Za-z0-9_]/g, "_")
+      .toUpperCase();
+    return /^[0-9]/.test(ident) ? `_${ident}` : ident;
+  }
+
   private toSnakeCase(str: string): string {
     return str
       .replace(/([A-Z])/g, "_$1")
       .toLowerCase()
-      .replace(/^_/, "")
-      .replace(/-/g, "_");
+      .replace(/^_/, "");
   }
 
   private formatDocComment(doc: string): string {
@@ -7,10 +7,22 @@
 
 import * as fs from "node:fs";
 import * as path from "node:path";
+import { performance } from "node:perf_hooks";
 import { TypeResolver } from "./resolver";
 import { RustGenerator } from "./generator";
-import type { ConversionOptions, ConversionResult, CollectedType } from "./types";
+import { ResolutionCache } from "./cache";
+import { diffGenerated } from "./diff";
+import { createDiagnostic } from "./diagnostics";
+import { mergeKeptRegions, parseKeptRegions } from "./keep";
+import { verifyRustTypes } from "./verify";
+import { findTsConfig } from "./module-resolution";
+import { getJobName, getJobOptions, type JobOptions, type ProjectConfig } from "./config";
+import type { ConversionOptions, ConversionResult, CollectedType, Diagnostic, TypeSelection, VerifyResult } from "./types";
 export { TypeConversionError } from "./types";
+export { clearCache } from "./cache";
+export { DEFAULT_LINT_LEVELS, DIAGNOSTIC_CODES, formatDiagnostic } from "./diagnostics";
+export { CONFIG_FILE_NAMES, defineConfig, findConfig, loadConfig, validateConfig } from "./config";
+export type { JobConfig, JobOptions, ProjectConfig } from "./config";
 export type {
   ConversionOptions,
   ConversionResult,
@@ -22,6 +34,21 @@ export type {
   StructField,
   EnumVariant,
   UnionVariant,
+  ExternalTypeUsage,
+  ConversionStats,
+  TypeSelection,
+  FileDrift,
+  Diagnostic,
+  DiagnosticName,
+  LintName,
+  LintLevel,
+  Manifest,
+  ManifestItem,
+  ManifestField,
+  ManifestVariant,
+  ManifestFallback,
+  SourceLocation,
+  VerifyResult,
 } from "./types";
 
 /**
@@ -49,42 +76,199 @@ export async function convert(options: ConversionOptions): Promise<ConversionRes
   if (!fs.existsSync(entryFile)) {
     throw new Error(`Entry file not found: ${entryFile}`);
   }
+  if (options.checkOnly && !options.outputPath && !options.outputDir) {
+    throw new Error("checkOnly needs an outputPath or an outputDir to compare with");
+  }
 
   const resolverOptions: ConversionOptions = {
     ...options,
     entryFile,
+    ...(options.tsConfigPath ? { tsConfigPath: path.resolve(options.tsConfigPath) } : {}),
   };
 
-  const resolver = new TypeResolver(resolverOptions);
-  const collectedTypes = resolver.resolve();
-  const resolverWarnings = resolver.getWarnings();
+  const start = performance.now();
+  const cache = options.cacheDir ? new ResolutionCache(path.resolve(options.cacheDir), resolverOptions) : undefined;
+  const cacheData = cache?.load();
+  const tsConfigPath = resolverOptions.tsConfigPath ?? findTsConfig(entryFile);
+  const unchanged = cacheData ? cache!.getUnchangedResult(cacheData, tsConfigPath) : undefined;
+
+  let collectedTypes: CollectedType[];
+  let resolverWarnings: Diagnostic[];
+  let selection: TypeSelection[];
+  let inputFiles: string[];
+  let loaded = start;
+  let sourceFiles = 0;
+  let reusedTypes: number;
+  if (unchanged) {
+    // Nothing changed since the cached conversion, so the project isn't even loaded
+    collectedTypes = unchanged.types;
+    resolverWarnings = unchanged.warnings;
+    selection = unchanged.selection;
+    inputFiles = Object.keys(cacheData!.files);
+    reusedTypes = collectedTypes.length;
+  } else {
+    const resolver = new TypeResolver(resolverOptions, cacheData);
+    loaded = performance.now();
+    collectedTypes = resolver.resolve();
+    resolverWarnings = resolver.getWarnings();
+    selection = resolver.getSelection();
+    inputFiles = resolver.getInputFiles();
+    sourceFiles = resolver.getLoadedFileCount();
+    reusedTypes = resolver.getReusedTypeCount();
+    cache?.save(resolver.getCacheData(collectedTypes, resolverWarnings));
+  }
+  const resolved = performance.now();
+  if (tsConfigPath) {
+    inputFiles.push(tsConfigPath);
+  }
+  const getStats = (generated: number, written: number): ConversionResult["stats"] =>
+    options.verbose
+      ? {
+        sourceFiles,
+        types: collectedTypes.length,
+        reusedTypes,
+        loadMs: loaded - start,
+        resolveMs: resolved - loaded,
+        generateMs: generated - resolved,
+        writeMs: written - generated,
+      }
+      : undefined;
 
   if (collectedTypes.length === 0) {
+    const stats = getStats(resolved, resolved);
     return {
       rustCode: "// No types found to convert\n",
       convertedTypes: [],
-      warnings: ["No exportable types found in the entry file", ...resolverWarnings],
+      warnings: [
+        createDiagnostic("no-types", "warning", "No exportable types found in the entry file", { file: entryFile }),
+        ...resolverWarnings,
+      ],
+      externalTypes: [],
+      rustPaths: {},
+      ...(stats ? { stats } : {}),
+      selection,
+      inputFiles,
+      ...(options.checkOnly ? { drift: [] } : {}),
     };
   }
 
   const generator = new RustGenerator(resolverOptions);
   const result = generator.generate(collectedTypes);
+  const generated = performance.now();
 
   // Combine warnings from resolver and generator
   result.warnings = [...resolverWarnings, ...result.warnings];
+  result.selection = selection;
+  result.inputFiles = inputFiles;
 
-  if (options.outputPath) {
+  const outputFiles = options.dryRun ? {} : getOutputFiles(options, result);
+  if (options.keepRegions) {
+    keepRegions(outputFiles, result);
+  }
+  if (options.dryRun) {
+    // Only report what would be generated
+  } else if (options.checkOnly) {
+    result.drift = [];
+    for (const [outputPath, code] of Object.entries(outputFiles)) {
+      const existing = fs.existsSync(outputPath) ? fs.readFileSync(outputPath, "utf-8") : undefined;
+      const diff = diffGenerated(path.relative(process.cwd(), outputPath), existing, code);
+      if (diff) {
+        result.drift.push({ path: outputPath, diff });
+      }
+    }
+  } else {
+    result.writtenFiles = [];
+    for (const [outputPath, code] of Object.entries(outputFiles)) {
+      // Unchanged files aren't rewritten, so tools watching them (cargo) don't rebuild
+      if (fs.existsSync(outputPath) && fs.readFileSync(outputPath, "utf-8") === code) {
+        continue;
+      }
+      fs.mkdirSync(path.dirname(outputPath), { recursive: true });
+      fs.writeFileSync(outputPath, code);
+      result.writtenFiles.push(outputPath);
+    }
+  }
+
+  const stats = getStats(generated, performance.now());
+  if (stats) {
+    result.stats = stats;
+  }
+  return result;
+}
+
+/**
+ * The code of each output file, by absolute path, followed by the manifest if any
+ */
+function getOutputFiles(options: ConversionOptions, result: ConversionResult): Record<string, string> {
+  let files: Record<string, string> = {};
+  let manifestPath: string | undefined;
+  if (options.outputDir && result.files) {
+    const outputDir = path.resolve(options.outputDir);
+    files = Object.fromEntries(Object.entries(result.files).map(([file, code]) => [path.join(outputDir, file), code]));
+    manifestPath = path.join(outputDir, "manifest.json");
+  } else if (options.outputPath) {
     const outputPath = path.resolve(options.outputPath);
-    const outputDir = path.dirname(outputPath);
+    files = { [outputPath]: result.rustCode };
+    manifestPath = outputPath.replace(/(\.rs)?$/, ".manifest.json");
+  }
+  if (result.manifest && manifestPath) {
+    files[manifestPath] = JSON.stringify(result.manifest, null, 2) + "\n";
+  }
+  return files;
+}
 
-    if (!fs.existsSync(outputDir)) {
-      fs.mkdirSync(outputDir, { recursive: true });
+/**
+ * Carry the code of the kept regions of the existing output files over to the generated code, warning about the
+ * regions whose type isn't generated anymore
+ */
+function keepRegions(outputFiles: Record<string, string>, result: ConversionResult): void {
+  for (const [outputPath, code] of Object.entries(outputFiles)) {
+    if (!outputPath.endsWith(".rs") || !fs.existsSync(outputPath)) {
+      continue;
+    }
+    const merged = mergeKeptRegions(code, parseKeptRegions(outputPath, fs.readFileSync(outputPath, "utf-8")));
+    outputFiles[outputPath] = merged.code;
+    for (const region of merged.orphaned) {
+      result.warnings.push(createDiagnostic(
+        "orphaned-region",
+        "warning",
+        `The code kept ${region.name ? `after '${region.name}' ` : ""}has no place in the generated code anymore, it was moved to the end of the file`,
+        { file: outputPath, line: region.line, column: 1 },
+      ));
     }
-
-    fs.writeFileSync(outputPath, result.rustCode);
   }
+}
 
-  return result;
+export interface JobResult {
+  name: string;
+  options: ConversionOptions;
+  result: ConversionResult;
+}
+
+/**
+ * Run the jobs of a project configuration (see `loadConfig`) in order. Types already generated by a previous job
+ * are referenced from its output instead of being generated again (see `sharedTypes`).
+ *
+ * @param config Project configuration, with absolute paths
+ * @param configDir Directory of the config file, jobs are named relative to it
+ * @param overrides Options applied over those of every job
+ */
+export async function convertProject(
+  config: ProjectConfig,
+  configDir: string,
+  overrides: Partial<JobOptions> = {},
+): Promise<JobResult[]> {
+  const sharedTypes: Record<string, string> = {};
+  const results: JobResult[] = [];
+  for (const job of config.jobs) {
+    const options: ConversionOptions = { ...getJobOptions(config, job), ...overrides, sharedTypes: { ...sharedTypes } };
+    const result = await convert(options);
+    for (const [key, rustPath] of Object.entries(result.rustPaths)) {
+      sharedTypes[key] ??= rustPath;
+    }
+    results.push({ name: getJobName(job, configDir), options, result });
+  }
+  return results;
 }
 
 /**
@@ -105,12 +289,54 @@ export function resolveTypes(options: ConversionOptions): CollectedType[] {
   const resolverOptions: ConversionOptions = {
     ...options,
     entryFile,
+    ...(options.tsConfigPath ? { tsConfigPath: path.resolve(options.tsConfigPath) } : {}),
   };
 
   const resolver = new TypeResolver(resolverOptions);
   return resolver.resolve();
 }
 
+/**
+ * Compare the structs and enums of a hand-written Rust file with the TypeScript types of the same name, by the JSON
+ * they serialize to: missing or extra fields, optionality, renames, field types and enum values. Nothing is generated.
+ *
+ * @param options Conversion options selecting the types, only those affecting the resolution are used
+ * @param rustFile The Rust file declaring the types
+ * @returns The differences, as errors located in the Rust file
+ *
+ * @example
+ * ```typescript
+ * const { mismatches } = await verify({ entryFile: './src/types.ts', typeNames: ['User'] }, '../server/src/models.rs');
+ * mismatches.forEach((m) => console.error(formatDiagnostic(m)));
+ * ```
+ */
+export async function verify(options: ConversionOptions, rustFile: string): Promise<VerifyResult> {
+  const entryFile = path.resolve(options.entryFile);
+  const rustPath = path.resolve(rustFile);
+
+  if (!fs.existsSync(entryFile)) {
+    throw new Error(`Entry file not found: ${entryFile}`);
+  }
+  if (!fs.existsSync(rustPath)) {
+    throw new Error(`Rust file not found: ${rustPath}`);
+  }
+
+  const resolver = new TypeResolver({
+    ...options,
+    entryFile,
+    ...(options.tsConfigPath ? { tsConfigPath: path.resolve(options.tsConfigPath) } : {}),
+  });
+  const types = resolver.resolve();
+  const { mismatches, verifiedTypes } = verifyRustTypes(
+    rustPath,
+    fs.readFileSync(rustPath, "utf-8"),
+    types,
+    resolver.getSelection(),
+    options,
+  );
+  return { mismatches, verifiedTypes, warnings: resolver.getWarnings() };
+}
+
 /**
  * Generate Rust code from pre-resolved types
  *
@@ -0,0 +1,91 @@
+/**
+ * Hand-written code kept between `// ts2rs:keep-start` and `// ts2rs:keep-end` when the output is generated again
+ */
+
+const START_MARKER = /^\s*\/\/ ts2rs:keep-start(?:\s+(\S+))?\s*$/;
+const END_MARKER = /^\s*\/\/ ts2rs:keep-end\b/;
+
+/**
+ * The code of a kept region, named after the type it follows (unnamed for the region at the end of the file)
+ */
+export interface KeptRegion {
+  name?: string;
+  line: number; // 1-based line of its start marker
+  lines: string[];
+}
+
+/**
+ * The markers of an empty region, generated after each type and at the end of the file when `keepRegions` is set
+ */
+export function keepSlot(name?: string): string[] {
+  return [`// ts2rs:keep-start${name ? ` ${name}` : ""}`, "// ts2rs:keep-end"];
+}
+
+/**
+ * The kept regions of a file, throwing if a region isn't closed (its code would be lost)
+ */
+export function parseKeptRegions(file: string, code: string): KeptRegion[] {
+  const regions: KeptRegion[] = [];
+  let current: KeptRegion | undefined;
+  code.split(/\r?\n/).forEach((line, i) => {
+    const start = START_MARKER.exec(line);
+    if (start) {
+      if (current) {
+        throw new Error(`${file}:${i + 1}: ts2rs:keep-start inside the region started at line ${current.line}`);
+      }
+      current = { ...(start[1] ? { name: start[1] } : {}), line: i + 1, lines: [] };
+    } else if (END_MARKER.test(line)) {
+      if (!current) {
+        throw new Error(`${file}:${i + 1}: ts2rs:keep-end without a ts2rs:keep-start`);
+      }
+      regions.push(current);
+      current = undefined;
+    } else {
+      current?.lines.push(line);
+    }
+  });
+  if (current) {
+    throw new Error(`${file}:${current.line}: ts2rs:keep-start without a ts2rs:keep-end`);
+  }
+  return regions;
+}
+
+/**
+ * Fill the slots of the generated code with the code of the kept regions of the same name. Regions without a slot
+ * are appended at the end of the file, so their code isn't lost, and returned as orphaned (with their new line).
+ */
+export function mergeKeptRegions(generated: string, regions: KeptRegion[]): { code: string; orphaned: KeptRegion[] } {
+  const remaining = [...regions];
+  const lines: string[] = [];
+  let skipping = false;
+  for (const line of generated.split("\n")) {
+    const start = START_MARKER.exec(line);
+    if (start) {
+      lines.push(line);
+      // Regions of the same name (e.g. split by mistake) are merged into one
+      const kept = remaining.filter((region) => region.name === start[1]);
+      lines.push(...kept.flatMap((region) => region.lines));
+      kept.forEach((region) => remaining.splice(remaining.indexOf(region), 1));
+      skipping = true;
+    } else if (END_MARKER.test(line)) {
+      lines.push(line);
+      skipping = false;
+    } else if (!skipping) {
+      lines.push(line);
+    }
+  }
+
+  const orphaned: KeptRegion[] = [];
+  if (remaining.length > 0) {
+    while (lines[lines.length - 1] === "") {
+      lines.pop();
+    }
+    for (const region of remaining) {
+      const [start, end] = keepSlot(region.name);
+      lines.push("", start!, ...region.lines, end!);
+      orphaned.push({ ...region, line: lines.length - region.lines.length - 1 });
+    }
+    lines.push("");
+  }
+  return { code: lines.join("\n"), orphaned };
+}
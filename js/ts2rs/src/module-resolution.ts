@@ -0,0 +1,280 @@
+/**
+ * Module resolution for monorepos, on top of TypeScript's own resolution (which handles `paths` and `extends`):
+ * workspace packages of referenced projects, package `exports` conditions, and sources of unbuilt referenced projects
+ */
+
+import * as path from "node:path";
+import { ts, type ResolutionHostFactory } from "ts-morph";
+
+/**
+ * A project referenced (directly or transitively) by the tsconfig
+ */
+export interface ReferencedProject {
+  configPath: string;
+  rootDir: string;
+  outDir?: string; // Where declarations are emitted
+  packageName?: string;
+  packageDir?: string;
+}
+
+/**
+ * Conditions of package `exports` tried in order, after any `customConditions` of the tsconfig
+ */
+const EXPORT_CONDITIONS = ["types", "import", "require", "node", "default"];
+
+const SOURCE_EXTENSIONS = [".ts", ".tsx", ".mts", ".cts", ".d.ts"];
+
+/**
+ * Collect the projects referenced by a tsconfig, following references of references
+ */
+export function getReferencedProjects(tsConfigPath: string, visited: Set<string> = new Set()): ReferencedProject[] {
+  const config = parseTsConfig(tsConfigPath);
+  if (!config) {
+    return [];
+  }
+
+  const projects: ReferencedProject[] = [];
+  for (const reference of config.projectReferences ?? []) {
+    const configPath = ts.resolveProjectReferencePath(reference);
+    if (visited.has(configPath) || !ts.sys.fileExists(configPath)) {
+      continue;
+    }
+    visited.add(configPath);
+
+    const referenced = parseTsConfig(configPath);
+    const configDir = path.dirname(configPath);
+    const packageJsonPath = ts.findConfigFile(configDir, ts.sys.fileExists, "package.json");
+    const packageJson = packageJsonPath ? readJson(packageJsonPath) : undefined;
+    const outDir = referenced?.options.declarationDir ?? referenced?.options.outDir;
+
+    projects.push({
+      configPath,
+      // Without `rootDir`, outputs mirror the common directory of the sources
+      rootDir: referenced?.options.rootDir ?? getCommonDir(referenced?.fileNames ?? []) ?? configDir,
+      ...(outDir ? { outDir } : {}),
+      ...(typeof packageJson?.name === "string" ? { packageName: packageJson.name, packageDir: path.dirname(packageJsonPath!) } : {}),
+    });
+    projects.push(...getReferencedProjects(configPath, visited));
+  }
+  return projects;
+}
+
+/**
+ * Resolve modules with TypeScript first, then as packages (from `node_modules` or the referenced projects)
+ * through their `exports`, `types` and `main` fields. Declaration outputs of referenced projects that were not
+ * built resolve to their sources.
+ */
+export function createResolutionHost(projects: ReferencedProject[]): ResolutionHostFactory {
+  return (moduleResolutionHost, getCompilerOptions) => ({
+    resolveModuleNames: (moduleNames, containingFile) => {
+      const options = getCompilerOptions();
+      return moduleNames.map((moduleName) =>
+        resolveModule(moduleName, containingFile, options, projects, moduleResolutionHost)
+      );
+    },
+  });
+}
+
+/**
+ * Resolve a module the way the resolution host does
+ */
+export function resolveModule(
+  moduleName: string,
+  containingFile: string,
+  options: ts.CompilerOptions,
+  projects: ReferencedProject[],
+  host: ts.ModuleResolutionHost = ts.sys,
+): ts.ResolvedModuleFull | undefined {
+  const resolved = ts.resolveModuleName(moduleName, containingFile, options, host).resolvedModule;
+  if (resolved) {
+    return resolved;
+  }
+  const fileName = resolvePackageImport(moduleName, containingFile, options, projects);
+  return fileName
+    ? {
+      resolvedFileName: fileName,
+      extension: getExtension(fileName),
+      isExternalLibraryImport: fileName.includes("/node_modules/"),
+    }
+    : undefined;
+}
+
+function resolvePackageImport(
+  moduleName: string,
+  containingFile: string,
+  options: ts.CompilerOptions,
+  projects: ReferencedProject[],
+): string | undefined {
+  const match = /^((?:@[^/]+\/)?[^/.][^/]*)(\/.*)?$/.exec(moduleName);
+  if (!match) {
+    return undefined; // Relative or absolute import
+  }
+  const [, packageName, subpath] = match;
+
+  const packageDir = findPackageDir(packageName!, path.dirname(containingFile)) ??
+    projects.find((p) => p.packageName === packageName)?.packageDir;
+  const packageJson = packageDir ? readJson(path.join(packageDir, "package.json")) : undefined;
+  if (!packageDir || !packageJson) {
+    return undefined;
+  }
+
+  const conditions = [...(options.customConditions ?? []), ...EXPORT_CONDITIONS];
+  const targets = packageJson.exports !== undefined
+    ? resolveExports(packageJson.exports, `.${subpath ?? ""}`, conditions)
+    : subpath
+      ? [`.${subpath}`]
+      : [packageJson.types, packageJson.typings, packageJson.main, "./index"].filter((t): t is string => typeof t === "string");
+
+  for (const target of targets) {
+    const fileName = findFile(path.resolve(packageDir, target)) ?? findSourceFile(path.resolve(packageDir, target), projects);
+    if (fileName) {
+      return fileName;
+    }
+  }
+  return undefined;
+}
+
+/**
+ * Get the targets of a subpath in package `exports`, in the order of the conditions
+ */
+function resolveExports(exports: unknown, subpath: string, conditions: string[]): string[] {
+  if (typeof exports === "string" || Array.isArray(exports)) {
+    return subpath === "." ? resolveExportTarget(exports, conditions) : [];
+  }
+  if (!exports || typeof exports !== "object") {
+    return [];
+  }
+
+  const entries = Object.entries(exports);
+  if (!entries.every(([key]) => key.startsWith("."))) {
+    return subpath === "." ? resolveExportTarget(exports, conditions) : []; // Conditions of the main export
+  }
+
+  const exact = entries.find(([key]) => key === subpath);
+  if (exact) {
+    return resolveExportTarget(exact[1], conditions);
+  }
+  for (const [key, target] of entries) {
+    const star = key.indexOf("*");
+    if (star !== -1 && subpath.startsWith(key.slice(0, star)) && subpath.endsWith(key.slice(star + 1))) {
+      const replacement = subpath.slice(star, subpath.length - (key.length - star - 1));
+      return resolveExportTarget(target, conditions).map((t) => t.replace(/\*/g, replacement));
+    }
+  }
+  return [];
+}
+
+function resolveExportTarget(target: unknown, conditions: string[]): string[] {
+  if (typeof target === "string") {
+    return [target];
+  }
+  if (Array.isArray(target)) {
+    return target.flatMap((t) => resolveExportTarget(t, conditions));
+  }
+  if (!target || typeof target !== "object") {
+    return [];
+  }
+  const entries = target as Record<string, unknown>;
+  return conditions.filter((c) => c in entries).flatMap((c) => resolveExportTarget(entries[c], conditions));
+}
+
+/**
+ * Find the declaration or source file of a package target (`./dist/index.js` => `./dist/index.d.ts`)
+ */
+function findFile(target: string): string | undefined {
+  if (SOURCE_EXTENSIONS.some((ext) => target.endsWith(ext)) && ts.sys.fileExists(target)) {
+    return target;
+  }
+  const base = target.replace(/\.[cm]?js$/, "");
+  return [...SOURCE_EXTENSIONS.map((ext) => base + ext), ...SOURCE_EXTENSIONS.map((ext) => path.join(base, "index" + ext))]
+    .find((candidate) => ts.sys.fileExists(candidate));
+}
+
+/**
+ * Map a missing output of a referenced project to its source (`shared/dist/index.d.ts` => `shared/src/index.ts`)
+ */
+function findSourceFile(target: string, projects: ReferencedProject[]): string | undefined {
+  for (const project of projects) {
+    const relative = project.outDir ? path.relative(project.outDir, target) : "..";
+    if (!relative.startsWith("..") && !path.isAbsolute(relative)) {
+      const fileName = findFile(path.join(project.rootDir, relative.replace(/(\.d)?\.[cm]?[jt]s$/, "")));
+      if (fileName) {
+        return fileName;
+      }
+    }
+  }
+  return undefined;
+}
+
+function getCommonDir(fileNames: string[]): string | undefined {
+  const dirs = fileNames.filter((f) => !/\.d\.[cm]?ts$/.test(f)).map((f) => path.dirname(f).split(/[\\/]/));
+  if (dirs.length === 0) {
+    return undefined;
+  }
+  let common = dirs[0]!.length;
+  for (const dir of dirs) {
+    while (common > 0 && dir.slice(0, common).join("/") !== dirs[0]!.slice(0, common).join("/")) {
+      common--;
+    }
+  }
+  return dirs[0]!.slice(0, common).join("/");
+}
+
+function findPackageDir(packageName: string, fromDir: string): string | undefined {
+  for (let dir = fromDir; ; dir = path.dirname(dir)) {
+    const candidate = path.join(dir, "node_modules", packageName);
+    if (ts.sys.fileExists(path.join(candidate, "package.json"))) {
+      return ts.sys.realpath?.(candidate) ?? candidate;
+    }
+    if (path.dirname(dir) === dir) {
+      return undefined;
+    }
+  }
+}
+
+function getExtension(fileName: string): ts.Extension {
+  const extensions: [string, ts.Extension][] = [
+    [".d.ts", ts.Extension.Dts],
+    [".d.mts", ts.Extension.Dmts],
+    [".d.cts", ts.Extension.Dcts],
+    [".tsx", ts.Extension.Tsx],
+    [".mts", ts.Extension.Mts],
+    [".cts", ts.Extension.Cts],
+  ];
+  return extensions.find(([ext]) => fileName.endsWith(ext))?.[1] ?? ts.Extension.Ts;
+}
+
+/**
+ * The nearest `tsconfig.json` of a file
+ */
+export function findTsConfig(fileName: string): string | undefined {
+  return ts.findConfigFile(fileName, ts.sys.fileExists, "tsconfig.json");
+}
+
+export function parseTsConfig(configPath: string): ts.ParsedCommandLine | undefined {
+  return ts.getParsedCommandLineOfConfigFile(configPath, {}, {
+    ...ts.sys,
+    onUnRecoverableConfigFileDiagnostic: () => {},
+  });
+}
+
+// `declare module "..." {` or `declare global {` at the start of a line
+const AUGMENTATION_PATTERN = /^\s*(?:export\s+)?declare\s+(?:module\s+["']|global\b)/m;
+
+/**
+ * The files of a tsconfig loaded even when nothing imports them: declaration files, which may declare globals,
+ * and files augmenting modules or the global scope, which may merge fields into interfaces
+ */
+export function getGlobalFileNames(configPath: string): string[] {
+  return (parseTsConfig(configPath)?.fileNames ?? []).filter(
+    (fileName) => /\.d\.[cm]?ts$/.test(fileName) || AUGMENTATION_PATTERN.test(ts.sys.readFile(fileName) ?? ""),
+  );
+}
+
+function readJson(filePath: string): Record<string, any> | undefined {
+  try {
+    return JSON.parse(ts.sys.readFile(filePath) ?? "");
+  } catch {
+    return undefined;
+  }
+}
@@ -10,14 +10,22 @@ import {
   TypeAliasDeclaration,
   InterfaceDeclaration,
   EnumDeclaration,
+  ClassDeclaration,
+  ModuleDeclaration,
   Node,
   SyntaxKind,
   PropertySignature,
+  PropertyDeclaration,
+  ParameterDeclaration,
+  VariableDeclaration,
+  VariableDeclarationKind,
+  Scope,
   ts,
 } from "ts-morph";
 import type {
   ResolvedType,
   CollectedType,
+  TypeSelection,
   StructType,
   EnumType,
   StructField,
@@ -26,8 +34,66 @@ import type {
   UnionVariant,
   ConversionOptions,
   TypeAliasType,
+  TemplateLiteralPart,
+  ConstantValue,
+  Diagnostic,
+  DiagnosticName,
+  LintLevel,
+  LintName,
+  SourceLocation,
 } from "./types";
 import { TypeConversionError } from "./types";
+import {
+  createDiagnostic,
+  getAllowedLints,
+  getLintLevel,
+  getNodeLocation,
+  type DiagnosticDetails,
+} from "./diagnostics";
+import {
+  createResolutionHost,
+  findTsConfig,
+  getGlobalFileNames,
+  getReferencedProjects,
+  resolveModule,
+  type ReferencedProject,
+} from "./module-resolution";
+import { getConfigHash, getFileHash, type CacheData, type ResolutionEvent } from "./cache";
+
+type TypeDeclaration = InterfaceDeclaration | TypeAliasDeclaration | EnumDeclaration | ClassDeclaration;
+
+/**
+ * Where diagnostics point to, and the lints allowed there by `@ts2rs-allow`
+ */
+type DiagnosticLocation = { node?: Node; fieldPath: string[]; allowedLints: LintName[] };
+
+function isTypeDeclaration(node: Node): node is TypeDeclaration {
+  return Node.isInterfaceDeclaration(node) || Node.isTypeAliasDeclaration(node) || Node.isEnumDeclaration(node) || Node.isClassDeclaration(node);
+}
+
+/**
+ * Whether a `typeNames` entry is a pattern rather than a type name
+ */
+function isTypePattern(typeName: string): boolean {
+  return /[*?|!]/.test(typeName);
+}
+
+/**
+ * Match a name against type patterns: globs (`Api*`) with alternatives (`*Request|*Response`), excluded with `!`.
+ * Returns the pattern the name matched (`true` if there are only excluding patterns), or `false`.
+ */
+function matchTypePatterns(name: string, patterns: string[]): string | boolean {
+  const matches = (pattern: string) =>
+    pattern.split("|").some((glob) => {
+      const source = glob.trim().replace(/[.+^$()[\]{}\\]/g, "\\$&").replace(/\*/g, ".*").replace(/\?/g, ".");
+      return new RegExp(`^${source}$`).test(name);
+    });
+  if (patterns.some((p) => p.startsWith("!") && matches(p.slice(1)))) {
+    return false;
+  }
+  const includes = patterns.filter((p) => !p.startsWith("!"));
+  return includes.length === 0 || (includes.find(matches) ?? false);
+}
 
 /**
  * Resolves TypeScript types to intermediate representation for Rust code generation
@@ -37,34 +103,187 @@ export class TypeResolver {
   private collectedTypes: Map<string, CollectedType> = new Map();
   private processingTypes: Set<string> = new Set(); // For cycle detection
   private typeParameters: Set<string> = new Set(); // Track current type parameters
+  private fieldNameHint: { name: string; field: string } | undefined; // Name for structs synthesized from inline mapped types, and the field they are for
+  private inlineTypeKeys: Map<string, string> = new Map(); // `<owner key>.<field>` => key of the struct synthesized for it
   private options: ConversionOptions;
-  private warnings: string[] = []; // Track warnings during resolution
-
-  constructor(options: ConversionOptions) {
+  private warnings: Diagnostic[] = []; // Track warnings during resolution
+  private errors: Diagnostic[] = []; // Errors reported together once everything is resolved
+  private location: DiagnosticLocation = { fieldPath: [], allowedLints: [] }; // Declaration and field being resolved
+  private externalTypes: Map<string, { package?: string; rustModule: string; name?: string }> = new Map(); // Type key => mapped package or shared type
+  private packageNames: Map<string, string | undefined> = new Map(); // Directory => name of its package
+  private exportNames: Map<string, string[]> = new Map(); // Type key => names the entry file exports it under
+  // Per-file indexes, built once per file on first lookup
+  private declarationIndex: Map<string, Map<string, TypeDeclaration>> = new Map(); // File => top-level types by name
+  private exportIndex: Map<string, ReadonlyMap<string, Node[]>> = new Map(); // File => exported declarations by name
+  private scopeIndex: Map<string, Map<string, TypeDeclaration>> = new Map(); // File => types in scope by name
+  // Incremental resolution (see `resolveKey`)
+  private configHash: string;
+  private globalFiles: string[] = []; // Declaration and augmenting files loaded from the tsconfig
+  private referencedProjects: ReferencedProject[] = []; // Projects referenced by the tsconfig, to resolve modules from
+  private recording: ResolutionEvent[] | undefined; // Events of the type being resolved
+  private records: Map<string, ResolutionEvent[]> = new Map(); // Type key => its resolution in this run
+  private cachedRecords: Map<string, ResolutionEvent[]> = new Map(); // Type key => reusable resolution of a previous run
+  // Type selection (see `getSelection`)
+  private currentKey: string | undefined; // Key of the type being resolved
+  private selecting: Omit<TypeSelection, "name" | "sourceFile"> | undefined; // Why the top-level type being resolved is selected
+  private selections: Map<string, Omit<TypeSelection, "name" | "sourceFile">> = new Map(); // Type key => why it was selected, dependencies are detailed by key
+  private excludedTypes: Map<string, string | undefined> = new Map(); // Type key => Rust type replacing it (`@ts2rs-exclude`)
+  private typeNames: Map<string, string> = new Map(); // Type key => assigned name
+
+  constructor(options: ConversionOptions, cache?: CacheData) {
     this.options = options;
-    const tsConfigPath = this.findTsConfig(options.entryFile);
+    const tsConfigPath = options.tsConfigPath ?? findTsConfig(options.entryFile);
+    if (tsConfigPath && !ts.sys.fileExists(tsConfigPath)) {
+      throw new Error(`tsconfig not found: ${tsConfigPath}`);
+    }
+    if (tsConfigPath) {
+      this.referencedProjects = getReferencedProjects(tsConfigPath);
+    }
     this.project = new Project({
+      // The tsconfig provides the compiler options and module resolution, files are loaded from the entry file
       tsConfigFilePath: tsConfigPath,
-      // Don't skip tsconfig files - we need them for module resolution
-      skipAddingFilesFromTsConfig: false,
+      skipAddingFilesFromTsConfig: true,
       compilerOptions: {
         // Enable strictNullChecks to properly handle T | null unions
         strictNullChecks: true,
       },
+      // Resolve workspace packages, package `exports` and unbuilt referenced projects
+      ...(tsConfigPath ? { resolutionHost: createResolutionHost(this.referencedProjects) } : {}),
     });
-    // Make sure the entry file is in the project
-    if (!this.project.getSourceFile(options.entryFile)) {
-      this.project.addSourceFilesAtPaths(options.entryFile);
+    // Load the entry file and the files reachable through its imports, plus the declaration files of the
+    // tsconfig, which may declare globals without being imported, and its files augmenting modules or the
+    // global scope, which may merge fields into interfaces without being imported either
+    this.project.addSourceFileAtPath(options.entryFile);
+    if (tsConfigPath) {
+      for (const fileName of getGlobalFileNames(tsConfigPath)) {
+        this.project.addSourceFileAtPath(fileName);
+        this.globalFiles.push(this.project.getSourceFileOrThrow(fileName).getFilePath());
+      }
+    }
+    this.project.resolveSourceFileDependencies();
+
+    this.configHash = getConfigHash(tsConfigPath);
+    if (cache) {
+      this.cachedRecords = this.getReusableRecords(cache);
+    }
+  }
+
+  /**
+   * Number of source files loaded into the project
+   */
+  getLoadedFileCount(): number {
+    return this.project.getSourceFiles().length;
+  }
+
+  /**
+   * The files the types were resolved from: the loaded source files and the declaration files of the packages
+   * they import, but not TypeScript's default libraries
+   */
+  getInputFiles(): string[] {
+    const program = this.project.getProgram().compilerObject;
+    return program
+      .getSourceFiles()
+      .filter((sourceFile) => !program.isSourceFileDefaultLibrary(sourceFile))
+      .map((sourceFile) => sourceFile.fileName);
+  }
+
+  /**
+   * Number of types whose resolution was reused from the cache
+   */
+  getReusedTypeCount(): number {
+    let count = 0;
+    for (const key of this.records.keys()) {
+      if (this.cachedRecords.has(key)) {
+        count++;
+      }
     }
+    return count;
   }
 
-  private findTsConfig(entryFile: string): string | undefined {
-    const configPath = ts.findConfigFile(
-      entryFile,
-      ts.sys.fileExists,
-      "tsconfig.json",
+  /**
+   * Data to cache for the next run, given the resolved types and warnings
+   */
+  getCacheData(types: CollectedType[], warnings: Diagnostic[]): Omit<CacheData, "version"> {
+    const files: Record<string, string> = {};
+    for (const file of this.getInputFiles()) {
+      const fileHash = getFileHash(file);
+      if (fileHash !== undefined) {
+        files[file] = fileHash;
+      }
+    }
+    return {
+      config: this.configHash,
+      files,
+      globalFiles: this.globalFiles,
+      unresolvedImports: this.getUnresolvedImports(),
+      records: Object.fromEntries(this.records),
+      result: { types, warnings, selection: this.getSelection() },
+    };
+  }
+
+  /**
+   * Imports of the loaded files that don't resolve to a file, which files added later may resolve
+   */
+  private getUnresolvedImports(): CacheData["unresolvedImports"] {
+    const options = this.project.getCompilerOptions();
+    return this.project.getSourceFiles().flatMap((sourceFile) =>
+      sourceFile
+        .getImportStringLiterals()
+        .map((literal) => literal.getLiteralValue())
+        .filter((specifier) => !resolveModule(specifier, sourceFile.getFilePath(), options, this.referencedProjects))
+        .map((specifier) => ({ file: sourceFile.getFilePath() as string, specifier }))
     );
-    return configPath;
+  }
+
+  /**
+   * The cached resolutions that can be replayed: those of types whose files, and the files these import
+   * (transitively), are unchanged, and which only resolve types that can be replayed too.
+   * Nothing is reused if the compiler options, a global declaration file or a package's declaration file changed.
+   */
+  private getReusableRecords(cache: CacheData): Map<string, ResolutionEvent[]> {
+    const reusable = new Map<string, ResolutionEvent[]>();
+    if (cache.config !== this.configHash || cache.globalFiles.join() !== this.globalFiles.join()) {
+      return reusable;
+    }
+    const projectFiles = new Set(this.project.getSourceFiles().map((sourceFile) => sourceFile.getFilePath() as string));
+    const packageFiles = Object.entries(cache.files).filter(([file]) => !projectFiles.has(file));
+    if (packageFiles.some(([file, fileHash]) => getFileHash(file) !== fileHash)) {
+      return reusable;
+    }
+
+    const changed = new Set<string>();
+    const pending = this.project
+      .getSourceFiles()
+      .filter((sourceFile) => getFileHash(sourceFile.getFilePath()) !== cache.files[sourceFile.getFilePath()]);
+    while (pending.length > 0) {
+      const sourceFile = pending.pop()!;
+      if (!changed.has(sourceFile.getFilePath())) {
+        changed.add(sourceFile.getFilePath());
+        pending.push(...sourceFile.getReferencingSourceFiles());
+      }
+    }
+    if (this.globalFiles.some((file) => changed.has(file))) {
+      return reusable;
+    }
+
+    for (const [key, events] of Object.entries(cache.records)) {
+      const file = this.getKeyFile(key);
+      if (!changed.has(file) && this.project.getSourceFile(file)) {
+        reusable.set(key, events);
+      }
+    }
+    // Drop resolutions depending on ones that can't be reused, until none is left
+    let dropped = true;
+    while (dropped) {
+      dropped = false;
+      for (const [key, events] of reusable) {
+        if (events.some((e) => "resolve" in e && !reusable.has(e.resolve) && !this.isExternalType(e.resolve))) {
+          reusable.delete(key);
+          dropped = true;
+        }
+      }
+    }
+    return reusable;
   }
 
   /**
@@ -72,46 +291,401 @@ export class TypeResolver {
    */
   resolve(): CollectedType[] {
     const sourceFile = this.project.getSourceFileOrThrow(this.options.entryFile);
+    this.collectExportNames(sourceFile, []);
+
+    // Patterns select among the exported types, names select a type whether it is exported or not.
+    // Alongside names, excluding patterns only filter the names rather than selecting every other export.
+    const typeNames = this.options.typeNames ?? [];
+    const patterns = typeNames.filter(isTypePattern);
+    const names = typeNames.filter((name) => !isTypePattern(name));
+    if (names.length === 0 || patterns.some((pattern) => !pattern.startsWith("!"))) {
+      this.resolveAllExportedTypes(sourceFile, patterns);
+    }
+    const exclusions = patterns.filter((pattern) => pattern.startsWith("!"));
+    for (const typeName of names.filter((name) => matchTypePatterns(name, exclusions) !== false)) {
+      this.selecting = { reason: "listed" };
+      this.resolveTypeByName(sourceFile, typeName);
+    }
+    this.selecting = undefined;
 
-    if (this.options.typeNames && this.options.typeNames.length > 0) {
-      for (const typeName of this.options.typeNames) {
-        this.resolveTypeByName(sourceFile, typeName);
+    const types = this.assignTypeNames();
+    if (this.errors.length > 0) {
+      throw TypeConversionError.fromDiagnostics(this.errors);
+    }
+    return types;
+  }
+
+  /**
+   * Why each type was selected: listed in `typeNames`, exported, matching a pattern, referenced by another type,
+   * or excluded with `@ts2rs-exclude`. Available once resolved.
+   */
+  getSelection(): TypeSelection[] {
+    const selection: TypeSelection[] = [];
+    for (const [key, { reason, detail }] of this.selections) {
+      if (!this.typeNames.has(key) && !this.excludedTypes.has(key)) {
+        continue; // Not collected, e.g. a union with unresolvable variants
+      }
+      selection.push({
+        name: this.typeNames.get(key) ?? this.getKeyName(key),
+        sourceFile: this.getKeyFile(key),
+        reason,
+        ...(detail !== undefined
+          ? { detail: reason === "dependency" ? this.typeNames.get(detail) ?? this.getKeyName(detail) : detail }
+          : {}),
+      });
+    }
+    return selection;
+  }
+
+  /**
+   * Assign the final names of the collected types, which are collected under their type key (see `getTypeKey`).
+   * Each type gets its (namespace-qualified) name unless renamed through `typeRenames`. Types whose names still
+   * collide are moved into namespaces derived from the differing part of their file paths
+   * (`billing/types.ts` => `Billing.Status`), or rejected in strict mode.
+   */
+  private assignTypeNames(): CollectedType[] {
+    const names = new Map<string, string>();
+    const keysByName = new Map<string, string[]>();
+    for (const key of this.collectedTypes.keys()) {
+      const name = this.getTypeRename(key) ?? this.getExportName(key) ?? this.getKeyName(key);
+      names.set(key, name);
+      keysByName.set(name, [...(keysByName.get(name) ?? []), key]);
+    }
+
+    const takenNames = new Set(names.values());
+    const originalNames = new Map<string, string>();
+    for (const [name, keys] of keysByName) {
+      if (keys.length < 2) {
+        continue;
+      }
+
+      // Each file is a separate module in an output directory, where the names only need to be unique internally
+      const files = keys.map((key) => this.getKeyFile(key));
+      const level: LintLevel = this.options.outputDir ||
+          keys.some((key) => this.collectedTypes.get(key)?.allowedLints?.includes("type-collision"))
+        ? "allow"
+        : getLintLevel("type-collision", this.options);
+      if (level === "deny") {
+        this.errors.push(createDiagnostic(
+          "type-collision",
+          "error",
+          `Types named '${name}' are declared in several files (${files.join(", ")})`,
+          { file: files[0]!, typeName: name, suggestion: `rename them with typeRenames (e.g. "${files[0]}#${name}")` },
+        ));
+        continue;
+      }
+
+      const prefixes = this.getDisambiguatingPrefixes(files);
+      const renamed: string[] = [];
+      let keptName = false;
+      keys.forEach((key, i) => {
+        const prefix = prefixes[i]!;
+        if (prefix.length === 0 && !keptName) {
+          keptName = true;
+          return; // The type closest to the other files keeps its name
+        }
+        const base = [...prefix, name].join(".");
+        let candidate = base;
+        for (let n = 2; takenNames.has(candidate); n++) {
+          candidate = `${base}${n}`;
+        }
+        takenNames.add(candidate);
+        names.set(key, candidate);
+        originalNames.set(key, name);
+        renamed.push(`'${candidate}' (${files[i]})`);
+      });
+      if (level === "warn") {
+        this.addWarning(createDiagnostic(
+          "type-collision",
+          "warning",
+          `Types named '${name}' are declared in several files and were disambiguated as ${renamed.join(", ")}`,
+          { file: files[0]!, typeName: name, suggestion: "use typeRenames to choose their names" },
+        ));
+      }
+    }
+
+    this.typeNames = names;
+    return Array.from(this.collectedTypes, ([key, collected]) => ({
+      ...collected,
+      name: names.get(key)!,
+      key,
+      ...(originalNames.has(key) ? { originalName: originalNames.get(key)! } : {}),
+      type: collected.type.kind === "constant"
+        ? { ...collected.type, name: names.get(key)! }
+        : this.renameTypeReferences(collected.type, names) as CollectedType["type"],
+    }));
+  }
+
+  /**
+   * Record the names the types of a file are exported under, following re-exports (`export { User as Member }`,
+   * `export * from`) and namespace re-exports (`export * as models from` => `models.User`)
+   */
+  private collectExportNames(sourceFile: SourceFile, namespace: string[], visited: Set<SourceFile> = new Set()): void {
+    visited.add(sourceFile);
+    for (const [name, declarations] of this.getExportedDeclarations(sourceFile)) {
+      for (const decl of declarations) {
+        if (isTypeDeclaration(decl)) {
+          const key = this.getTypeKey(decl);
+          // Default exports keep their declared name
+          const exportName = [...namespace, name === "default" ? this.getKeyName(key) : name].join(".");
+          this.exportNames.set(key, [...(this.exportNames.get(key) ?? []), exportName]);
+        } else if (Node.isSourceFile(decl) && !visited.has(decl)) {
+          this.collectExportNames(decl, [...namespace, name], visited);
+        }
       }
-    } else {
-      this.resolveAllExportedTypes(sourceFile);
     }
+  }
 
-    return Array.from(this.collectedTypes.values());
+  /**
+   * Get the public name of a type exported from the entry file under another name than it was declared with
+   */
+  private getExportName(key: string): string | undefined {
+    const exportNames = this.exportNames.get(key);
+    return exportNames && !exportNames.includes(this.getKeyName(key)) ? exportNames[0] : undefined;
+  }
+
+  /**
+   * Get the `typeRenames` entry for a type key. Entries are keyed by `<file>#<name>`, where the file is matched
+   * against the end of the declaring file's path, or by the name alone.
+   */
+  private getTypeRename(key: string): string | undefined {
+    const keyFile = this.getKeyFile(key).replace(/\\/g, "/");
+    for (const [target, rename] of Object.entries(this.options.typeRenames ?? {})) {
+      const separator = target.lastIndexOf("#");
+      const name = target.slice(separator + 1);
+      const file = target.slice(0, Math.max(separator, 0)).replace(/\\/g, "/").replace(/^\.\//, "");
+      if (name === this.getKeyName(key) && (!file || keyFile === file || keyFile.endsWith(`/${file}`))) {
+        return rename;
+      }
+    }
+    return undefined;
+  }
+
+  /**
+   * Derive namespaces from the part of each file path that differs from the others,
+   * e.g. `src/shared/types.ts` and `src/billing/types.ts` => `["Shared"]` and `["Billing"]`
+   */
+  private getDisambiguatingPrefixes(files: string[]): string[][] {
+    const segments = files.map((file) =>
+      file.replace(/\\/g, "/").replace(/(\.d)?\.[cm]?tsx?$/, "").split("/").filter((s) => s),
+    );
+    const shortest = Math.min(...segments.map((s) => s.length));
+
+    let leading = 0;
+    while (leading < shortest && segments.every((s) => s[leading] === segments[0]![leading])) {
+      leading++;
+    }
+    let trailing = 0;
+    while (
+      trailing < shortest - leading &&
+      segments.every((s) => s[s.length - 1 - trailing] === segments[0]![segments[0]!.length - 1 - trailing])
+    ) {
+      trailing++;
+    }
+
+    return segments.map((s) => s.slice(leading, s.length - trailing).map((segment) => this.toPascalCase(segment)));
+  }
+
+  /**
+   * Replace type keys in a resolved type (its own name and the types it references) with their final names
+   */
+  private renameTypeReferences(type: ResolvedType, names: Map<string, string>): ResolvedType {
+    const rename = (t: ResolvedType) => this.renameTypeReferences(t, names);
+    switch (type.kind) {
+      case "struct": {
+        if (this.excludedTypes.has(type.name)) {
+          const rustType = this.excludedTypes.get(type.name);
+          return rustType ? { kind: "external", name: rustType, rustModule: "" } : { kind: "json_value" };
+        }
+        const external = this.externalTypes.get(type.name);
+        if (external) {
+          return {
+            kind: "external",
+            name: this.getKeyName(type.name),
+            ...external,
+            ...(type.typeArguments ? { typeArguments: type.typeArguments.map(rename) } : {}),
+          };
+        }
+        return {
+          ...type,
+          name: names.get(type.name) ?? type.name,
+          fields: type.fields.map((f) => ({ ...f, type: rename(f.type) })),
+          ...(type.typeArguments ? { typeArguments: type.typeArguments.map(rename) } : {}),
+        };
+      }
+      case "enum":
+        return { ...type, name: names.get(type.name) ?? type.name };
+      case "union":
+        return {
+          ...type,
+          name: names.get(type.name) ?? type.name,
+          variants: type.variants.map((v) => ({ ...v, type: v.type && rename(v.type) })),
+        };
+      case "type_alias":
+        return { ...type, name: names.get(type.name) ?? type.name, aliasedType: rename(type.aliasedType) };
+      case "array":
+      case "set":
+        return { ...type, elementType: rename(type.elementType) };
+      case "option":
+      case "box":
+        return { ...type, innerType: rename(type.innerType) };
+      case "tuple":
+        return { ...type, elements: type.elements.map(rename) };
+      case "record":
+      case "map":
+        return { ...type, keyType: rename(type.keyType), valueType: rename(type.valueType) };
+      default:
+        return type;
+    }
   }
 
   /**
    * Get all warnings generated during resolution
    */
-  getWarnings(): string[] {
+  getWarnings(): Diagnostic[] {
     return this.warnings;
   }
 
+  private addWarning(diagnostic: Diagnostic): void {
+    this.warnings.push(diagnostic);
+    this.recording?.push({ warning: diagnostic });
+  }
+
   /**
-   * Handle falling back to serde_json::Value
-   * In strict mode, this throws an error. Otherwise, it logs a warning and returns JsonValueType.
+   * Warn about the declaration or field being resolved
+   */
+  private warn(name: DiagnosticName, message: string, suggestion?: string): void {
+    this.addWarning(createDiagnostic(name, "warning", message, {
+      ...this.getLocation(),
+      ...(suggestion ? { suggestion } : {}),
+    }));
+  }
+
+  /**
+   * Report a lint about the declaration or field being resolved (unless `details` locate it elsewhere), at its
+   * level: ignored, a warning, or an error
+   */
+  private lint(name: LintName, message: string, details: DiagnosticDetails = this.getLocation()): void {
+    const level = this.getLintLevel(name);
+    if (level === "warn") {
+      this.addWarning(createDiagnostic(name, "warning", message, details));
+    } else if (level === "deny") {
+      this.errors.push(createDiagnostic(name, "error", message, details));
+    }
+  }
+
+  /**
+   * The level of a lint where it is reported, `allow` if allowed there by `@ts2rs-allow`
+   */
+  private getLintLevel(name: LintName): LintLevel {
+    return this.location.allowedLints.includes(name) ? "allow" : getLintLevel(name, this.options);
+  }
+
+  /**
+   * Report an error about `node`, or the field or declaration being resolved (in `file` outside of one). Errors are
+   * thrown together once everything is resolved, so resolution goes on with a fallback.
+   */
+  private fail(
+    name: DiagnosticName,
+    message: string,
+    at: { node?: Node; file?: string; typeName?: string; suggestion?: string } = {},
+  ): void {
+    const { file = at.file, ...details } = this.getLocation(at.node);
+    this.errors.push(createDiagnostic(name, "error", message, {
+      ...details,
+      ...(file ? { file } : {}),
+      ...(at.typeName ? { typeName: at.typeName } : {}),
+      ...(at.suggestion ? { suggestion: at.suggestion } : {}),
+    }));
+  }
+
+  /**
+   * Where a diagnostic points to: `node`, or the field or declaration being resolved, with the type and field path
+   */
+  private getLocation(node?: Node): DiagnosticDetails {
+    const { fieldPath } = this.location;
+    const locationNode = node ?? this.location.node;
+    return {
+      ...(locationNode ? getNodeLocation(locationNode) : {}),
+      ...(this.currentKey !== undefined ? { typeName: this.getKeyName(this.currentKey) } : {}),
+      ...(fieldPath.length > 0 ? { fieldPath: fieldPath.join(".") } : {}),
+    };
+  }
+
+  /**
+   * Where a field or variant is declared, for `sourceComments`
+   */
+  private getSource(node: Node | undefined): { source?: SourceLocation } {
+    return node ? { source: { file: node.getSourceFile().getFilePath(), line: node.getStartLineNumber() } } : {};
+  }
+
+  /**
+   * Collect a type, with the line it is declared at and the lints allowed there
+   */
+  private collect(key: string, type: CollectedType): void {
+    const { node, allowedLints } = this.location;
+    const collected = {
+      ...type,
+      ...(node && type.line === undefined ? { line: node.getStartLineNumber() } : {}),
+      ...(allowedLints.length > 0 && !type.allowedLints ? { allowedLints } : {}),
+    };
+    this.collectedTypes.set(key, collected);
+    this.recording?.push({ collect: key, type: collected });
+    this.noteSelection(key);
+  }
+
+  /**
+   * Keep a type out of the output (`@ts2rs-exclude`), its references become `rustType` or `serde_json::Value`
+   */
+  private exclude(key: string, rustType: string | undefined): void {
+    this.excludedTypes.set(key, rustType);
+    this.recording?.push({ exclude: key, ...(rustType ? { rustType } : {}) });
+    this.selections.set(key, { reason: "excluded", detail: rustType ?? "serde_json::Value" });
+  }
+
+  /**
+   * Record why a type is selected: as a dependency of the type being resolved, or for the reason the top-level
+   * type being resolved was selected (which takes precedence over being a dependency)
+   */
+  private noteSelection(key: string): void {
+    const existing = this.selections.get(key);
+    if (this.currentKey !== undefined) {
+      if (!existing) {
+        this.selections.set(key, { reason: "dependency", detail: this.currentKey });
+      }
+    } else if (!existing || existing.reason === "dependency") {
+      this.selections.set(key, this.selecting ?? { reason: "exported" });
+    }
+  }
+
+  /**
+   * Record that the type being resolved uses a type collected before (e.g. an inline type shared with another),
+   * so replaying its resolution collects it too
+   */
+  private recordCollected(key: string): void {
+    const collected = this.collectedTypes.get(key);
+    if (collected) {
+      this.recording?.push({ collect: key, type: collected });
+    }
+  }
+
+  /**
+   * Handle falling back to serde_json::Value, reported as the `value-fallback` lint (denied in strict mode).
+   * The diagnostic points to the field or declaration being resolved, or to `sourceFile` outside of one.
    */
   private handleValueFallback(
     reason: string,
     type?: Type,
     sourceFile?: string,
   ): ResolvedType {
-    const message = `Falling back to serde_json::Value: ${reason}${sourceFile ? ` (in ${sourceFile})` : ""}${type ? ` (type: ${type.getText()})` : ""}`;
-    
-    if (this.options.strict) {
-      throw new TypeConversionError(
-        type?.getText() ?? "unknown",
-        reason,
-        sourceFile,
-      );
-    }
-    
-    this.warnings.push(message);
-    return { kind: "json_value" };
+    const typeText = type ? ` (type: ${type.getText()})` : "";
+    const denied = this.getLintLevel("value-fallback") === "deny";
+    this.lint(
+      "value-fallback",
+      denied ? `${reason}${typeText}` : `Falling back to serde_json::Value: ${reason}${typeText}`,
+      { ...(sourceFile ? { file: sourceFile } : {}), ...this.getLocation() },
+    );
+    return { kind: "json_value", fallback: `${reason}${typeText}` };
   }
 
   /**
@@ -182,102 +756,463 @@ export class TypeResolver {
     }
   }
 
-  private resolveAllExportedTypes(sourceFile: SourceFile): void {
-    const exportedDeclarations = sourceFile.getExportedDeclarations();
+  /**
+   * Resolve the exported types (and constants) of a file whose exported name (`models.User` for
+   * `export * as models`, `Api.User` for namespace members) matches the type patterns
+   */
+  private resolveAllExportedTypes(
+    sourceFile: SourceFile,
+    patterns: string[],
+    namespace: string[] = [],
+    visited: Set<SourceFile> = new Set(),
+  ): void {
+    visited.add(sourceFile);
+    const exportedDeclarations = this.getExportedDeclarations(sourceFile);
 
     for (const [name, declarations] of exportedDeclarations) {
       for (const decl of declarations) {
-        if (
-          Node.isInterfaceDeclaration(decl) ||
-          Node.isTypeAliasDeclaration(decl) ||
-          Node.isEnumDeclaration(decl)
+        if (isTypeDeclaration(decl)) {
+          const exportName = [...namespace, name === "default" ? decl.getName() ?? name : name].join(".");
+          if (this.selectExport(exportName, patterns)) {
+            this.resolveDeclaration(decl);
+          }
+        } else if (Node.isModuleDeclaration(decl)) {
+          this.resolveNamespace(decl, patterns, [...namespace, name]);
+        } else if (Node.isSourceFile(decl)) {
+          // `export * as models from "./models"`
+          if (!visited.has(decl)) {
+            this.resolveAllExportedTypes(decl, patterns, [...namespace, name], visited);
+          }
+        } else if (
+          Node.isVariableDeclaration(decl) &&
+          // A value shadowed by a type of the same name (the `as const` enum-object pattern) is represented by that type
+          !declarations.some((d) => Node.isTypeAliasDeclaration(d) || Node.isInterfaceDeclaration(d) || Node.isClassDeclaration(d)) &&
+          this.selectExport([...namespace, name].join("."), patterns)
         ) {
-          this.resolveTypeByName(sourceFile, name);
+          this.resolveConstant(decl, false);
         }
       }
     }
+    this.selecting = undefined;
   }
 
-  private resolveTypeByName(sourceFile: SourceFile, typeName: string): void {
-    if (this.collectedTypes.has(typeName)) {
-      return; // Already resolved
+  /**
+   * Resolve the exported types of a namespace and of its nested namespaces
+   */
+  private resolveNamespace(declaration: ModuleDeclaration, patterns: string[], namespace: string[]): void {
+    for (const statement of declaration.getStatements()) {
+      if (Node.isModuleDeclaration(statement)) {
+        if (statement.isExported()) {
+          this.resolveNamespace(statement, patterns, [...namespace, statement.getName()]);
+        }
+      } else if (
+        (Node.isInterfaceDeclaration(statement) ||
+          Node.isTypeAliasDeclaration(statement) ||
+          Node.isEnumDeclaration(statement) ||
+          Node.isClassDeclaration(statement)) &&
+        statement.isExported() &&
+        this.selectExport([...namespace, statement.getName() ?? ""].join("."), patterns)
+      ) {
+        this.resolveDeclaration(statement);
+      }
     }
+  }
 
-    if (this.processingTypes.has(typeName)) {
-      return; // Cycle detected, skip
+  /**
+   * Whether an export is selected by the type patterns, noting why for the types it resolves
+   */
+  private selectExport(exportName: string, patterns: string[]): boolean {
+    const match = matchTypePatterns(exportName, patterns);
+    if (match === false) {
+      return false;
     }
+    this.selecting = match === true ? { reason: "exported" } : { reason: "pattern", detail: match };
+    return true;
+  }
 
+  /**
+   * Resolve the type (or constant) named `typeName` as seen from `sourceFile`. Returns its type key.
+   */
+  private resolveTypeByName(sourceFile: SourceFile, typeName: string): string {
     const declaration = this.findTypeDeclaration(sourceFile, typeName);
     if (!declaration) {
-      throw new TypeConversionError(
-        typeName,
-        "Type declaration not found",
-        sourceFile.getFilePath(),
-      );
+      const constant = this.getExportedDeclarations(sourceFile).get(typeName)?.find(Node.isVariableDeclaration) ??
+        sourceFile.getVariableDeclaration(typeName);
+      if (constant) {
+        return this.resolveConstant(constant, true);
+      }
+      this.fail("type-not-found", "Type declaration not found", { file: sourceFile.getFilePath(), typeName });
+      return `${sourceFile.getFilePath()}#${typeName}`;
     }
 
-    this.processingTypes.add(typeName);
+    return this.resolveDeclaration(declaration);
+  }
 
-    try {
-      if (Node.isInterfaceDeclaration(declaration)) {
+  /**
+   * Resolve a type declaration, collecting it under its type key (see `getTypeKey`).
+   * Returns the type key.
+   */
+  private resolveDeclaration(typeDeclaration: TypeDeclaration): string {
+    const declaration = this.getCanonicalDeclaration(typeDeclaration);
+    const name = this.getTypeKey(declaration);
+    const location: DiagnosticLocation = { node: declaration, fieldPath: [], allowedLints: this.getAllowedLints(declaration) };
+    this.resolveKey(name, () => this.withLocation(location, () => {
+      const excludeTag = declaration.getJsDocs().flatMap((doc) => doc.getTags()).find((tag) => tag.getTagName() === "ts2rs-exclude");
+      if (excludeTag) {
+        this.exclude(name, excludeTag.getCommentText()?.trim() || undefined);
+      } else if (Node.isInterfaceDeclaration(declaration)) {
         this.resolveInterface(declaration);
       } else if (Node.isTypeAliasDeclaration(declaration)) {
         this.resolveTypeAlias(declaration);
       } else if (Node.isEnumDeclaration(declaration)) {
         this.resolveEnum(declaration);
+      } else if (Node.isClassDeclaration(declaration)) {
+        this.resolveClass(declaration);
+      }
+    }));
+    return name;
+  }
+
+  /**
+   * Resolve the type with this key unless it is already resolved, a cycle, or declared by a package mapped to a
+   * Rust crate. What happens while resolving it (types collected, types it resolves, warnings) is recorded, and
+   * replayed instead of calling `resolve` when a reusable record was cached by a previous run.
+   */
+  private resolveKey(key: string, resolve: () => void): void {
+    if (this.isExternalType(key)) {
+      return;
+    }
+    this.recording?.push({ resolve: key });
+    this.noteSelection(key);
+    if (this.isResolved(key)) {
+      return;
+    }
+
+    const parentRecording = this.recording;
+    const parentKey = this.currentKey;
+    const parentLocation = this.location;
+    this.recording = [];
+    this.currentKey = key;
+    this.processingTypes.add(key);
+    try {
+      const cached = this.cachedRecords.get(key);
+      if (cached) {
+        // Replayed types were collected with the lints allowed where they are declared
+        this.location = { fieldPath: [], allowedLints: [] };
+        for (const event of cached) {
+          if ("resolve" in event) {
+            this.resolveKey(event.resolve, () => {
+              throw new Error(`Cached resolution of '${event.resolve}' is missing`); // Excluded by `getReusableRecords`
+            });
+          } else if ("collect" in event) {
+            if (this.collectedTypes.has(event.collect)) {
+              this.recordCollected(event.collect);
+            } else {
+              this.collect(event.collect, event.type);
+            }
+          } else if ("exclude" in event) {
+            this.exclude(event.exclude, event.rustType);
+          } else {
+            this.addWarning(event.warning);
+          }
+        }
+      } else {
+        resolve();
       }
+      this.records.set(key, this.recording);
     } finally {
-      this.processingTypes.delete(typeName);
+      this.processingTypes.delete(key);
+      this.recording = parentRecording;
+      this.currentKey = parentKey;
+      this.location = parentLocation;
+    }
+  }
+
+  /**
+   * Get the key a declaration is collected under while resolving: its declaring file and qualified name
+   * (`/src/billing/types.ts#Status`), so same-named types from different files stay distinct.
+   * Type names are assigned from the keys once everything is resolved (see `assignTypeNames`).
+   */
+  private getTypeKey(declaration: TypeDeclaration): string {
+    const canonical = this.getCanonicalDeclaration(declaration);
+    return `${canonical.getSourceFile().getFilePath()}#${this.getQualifiedName(canonical)}`;
+  }
+
+  /**
+   * Merged interfaces are one type, represented by their first declaration outside of a module augmentation
+   */
+  private getCanonicalDeclaration(declaration: TypeDeclaration): TypeDeclaration {
+    if (!Node.isInterfaceDeclaration(declaration)) {
+      return declaration;
+    }
+    const merged = (declaration.getType().getSymbol()?.getDeclarations() ?? []).filter(
+      (d): d is InterfaceDeclaration => Node.isInterfaceDeclaration(d),
+    );
+    return merged.find((d) => !this.isInModuleAugmentation(d)) ?? merged[0] ?? declaration;
+  }
+
+  /**
+   * Whether the type with this key has been collected, is being collected, or is an external or excluded type
+   */
+  private isResolved(key: string): boolean {
+    return this.collectedTypes.has(key) || this.processingTypes.has(key) || this.externalTypes.has(key) ||
+      this.excludedTypes.has(key);
+  }
+
+  /**
+   * Check if the type with this key is declared in a package mapped to a Rust crate (`packageCrates`) or generated
+   * by another conversion (`sharedTypes`), registering it as an external type (also when replaying the resolution
+   * of the type being resolved)
+   */
+  private isExternalType(key: string): boolean {
+    if (this.externalTypes.has(key)) {
+      this.recording?.push({ resolve: key });
+      return true;
+    }
+    const sharedPath = this.options.sharedTypes?.[key];
+    if (sharedPath !== undefined) {
+      const separator = sharedPath.lastIndexOf("::");
+      this.externalTypes.set(key, {
+        rustModule: sharedPath.slice(0, Math.max(separator, 0)),
+        name: sharedPath.slice(separator === -1 ? 0 : separator + 2),
+      });
+      this.recording?.push({ resolve: key });
+      return true;
+    }
+    const packageName = this.getPackageName(this.getKeyFile(key));
+    const crates = this.options.packageCrates ?? {};
+    const mapped = packageName !== undefined
+      ? Object.keys(crates).find((p) => p === packageName || `@types/${p}` === packageName)
+      : undefined;
+    if (mapped === undefined) {
+      return false;
+    }
+    this.externalTypes.set(key, { package: mapped, rustModule: crates[mapped]! });
+    this.recording?.push({ resolve: key });
+    return true;
+  }
+
+  /**
+   * Get the name of the package a file belongs to, from the nearest `package.json` with a name
+   */
+  private getPackageName(filePath: string): string | undefined {
+    const dir = filePath.slice(0, Math.max(filePath.replace(/\\/g, "/").lastIndexOf("/"), 0));
+    if (!dir || this.packageNames.has(dir)) {
+      return this.packageNames.get(dir);
+    }
+
+    const packageJsonPath = ts.findConfigFile(dir, ts.sys.fileExists, "package.json");
+    let name: string | undefined;
+    if (packageJsonPath) {
+      try {
+        name = JSON.parse(ts.sys.readFile(packageJsonPath) ?? "{}").name;
+      } catch {
+        name = undefined;
+      }
+      // Nested package.json files without a name (e.g. `{ "type": "module" }`) belong to the enclosing package
+      name ??= this.getPackageName(packageJsonPath.slice(0, packageJsonPath.lastIndexOf("/")));
+    }
+    this.packageNames.set(dir, name);
+    return name;
+  }
+
+  private getKeyName(key: string): string {
+    return key.slice(key.lastIndexOf("#") + 1);
+  }
+
+  private getKeyFile(key: string): string {
+    return key.slice(0, key.lastIndexOf("#"));
+  }
+
+  private isInModuleAugmentation(node: Node): boolean {
+    return node.getAncestors().some(
+      (a) => Node.isModuleDeclaration(a) && !ts.isIdentifier(a.compilerNode.name),
+    );
+  }
+
+  /**
+   * Get the name of a declaration prefixed by the enclosing namespaces,
+   * e.g. `Api.V1.User` for `namespace Api.V1 { export interface User {} }`
+   */
+  private getQualifiedName(declaration: TypeDeclaration): string {
+    return [...this.getNamespacePath(declaration), declaration.getName() ?? ""].join(".");
+  }
+
+  /**
+   * Get the namespaces enclosing a node, outermost first. Ambient module declarations
+   * (`declare module "pkg"`) and `declare global` end the path, since they don't introduce a namespace.
+   */
+  private getNamespacePath(node: Node): string[] {
+    const path: string[] = [];
+    for (const ancestor of node.getAncestors()) {
+      if (!Node.isModuleDeclaration(ancestor)) {
+        continue;
+      }
+      const nameNode = ancestor.compilerNode.name;
+      if (!ts.isIdentifier(nameNode) || ancestor.compilerNode.flags & ts.NodeFlags.GlobalAugmentation) {
+        break;
+      }
+      path.unshift(nameNode.text);
+    }
+    return path;
+  }
+
+  /**
+   * Get the type declaration a type name refers to, following imports and re-exports.
+   * Declarations from the TypeScript lib (`Array`, `Date`, ...) are not returned.
+   */
+  private getReferencedDeclaration(typeName: Node): TypeDeclaration | undefined {
+    const symbol = typeName.getSymbol();
+    const target = symbol?.isAlias() ? symbol.getAliasedSymbol() : symbol;
+    const declaration = target?.getDeclarations().find(isTypeDeclaration);
+    return declaration && !this.isFromTypeScriptLib(declaration.getSourceFile().getFilePath()) ? declaration : undefined;
+  }
+
+  /**
+   * Get the declaration a type name refers to if it is declared inside a namespace
+   * (`Api.User`, or `User` referenced from within `namespace Api`)
+   */
+  private getNamespacedDeclaration(typeName: Node): TypeDeclaration | undefined {
+    const declaration = this.getReferencedDeclaration(typeName);
+    return declaration && this.findNamespacedDeclaration([declaration]);
+  }
+
+  private findNamespacedDeclaration(declarations: Node[]): TypeDeclaration | undefined {
+    const declaration = declarations.find(isTypeDeclaration);
+    return declaration && this.getNamespacePath(declaration).length > 0 ? declaration : undefined;
+  }
+
+  /**
+   * Resolve a reference (found through the type checker) to a type declared inside a namespace
+   */
+  private resolveNamespacedReference(declaration: TypeDeclaration, type: Type, sourceFile: SourceFile): ResolvedType {
+    const name = this.resolveDeclaration(declaration);
+    if (!this.isResolved(name)) {
+      return this.handleValueFallback(
+        `Type '${this.getKeyName(name)}' could not be fully resolved`,
+        type,
+        sourceFile.getFilePath(),
+      );
     }
+    return { kind: "struct", name, fields: [] };
   }
 
   private findTypeDeclaration(
     sourceFile: SourceFile,
     typeName: string,
-  ): InterfaceDeclaration | TypeAliasDeclaration | EnumDeclaration | undefined {
-    // First check in the current file
-    let decl =
-      sourceFile.getInterface(typeName) ||
-      sourceFile.getTypeAlias(typeName) ||
-      sourceFile.getEnum(typeName);
+  ): TypeDeclaration | undefined {
+    // Namespace members can be requested by their qualified name (`Api.User`)
+    if (typeName.includes(".")) {
+      return this.findNamespaceMember(sourceFile, typeName.split("."));
+    }
 
+    // First check in the current file
+    const decl = this.getFileDeclarations(sourceFile).get(typeName);
     if (decl) {
       return decl;
     }
 
-    // Check in imported files
-    for (const importDecl of sourceFile.getImportDeclarations()) {
-      const resolvedSourceFile = importDecl.getModuleSpecifierSourceFile();
-
-      if (resolvedSourceFile) {
-        const namedImports = importDecl.getNamedImports();
-        for (const namedImport of namedImports) {
-          if (namedImport.getName() === typeName) {
-            decl =
-              resolvedSourceFile.getInterface(typeName) ||
-              resolvedSourceFile.getTypeAlias(typeName) ||
-              resolvedSourceFile.getEnum(typeName);
-            if (decl) {
-              return decl;
-            }
-          }
+    // Then what the file exports under this name, following re-exports and `export *`
+    const exported = this.getExportedDeclarations(sourceFile).get(typeName)?.find(isTypeDeclaration);
+    if (exported) {
+      return exported;
+    }
+
+    // Then what the name refers to in the file's scope (imports, including renamed ones, and global declarations)
+    return this.getScopeDeclarations(sourceFile).get(typeName);
+  }
+
+  /**
+   * The top-level type declarations of a file by name (interfaces first, like `getInterface` before `getTypeAlias`)
+   */
+  private getFileDeclarations(sourceFile: SourceFile): Map<string, TypeDeclaration> {
+    let index = this.declarationIndex.get(sourceFile.getFilePath());
+    if (!index) {
+      index = new Map();
+      const declarations = [
+        ...sourceFile.getInterfaces(),
+        ...sourceFile.getTypeAliases(),
+        ...sourceFile.getEnums(),
+        ...sourceFile.getClasses(),
+      ];
+      for (const declaration of declarations) {
+        const name = declaration.getName();
+        if (name && !index.has(name)) {
+          index.set(name, declaration);
         }
       }
+      this.declarationIndex.set(sourceFile.getFilePath(), index);
+    }
+    return index;
+  }
+
+  private getExportedDeclarations(sourceFile: SourceFile): ReadonlyMap<string, Node[]> {
+    let index = this.exportIndex.get(sourceFile.getFilePath());
+    if (!index) {
+      index = sourceFile.getExportedDeclarations();
+      this.exportIndex.set(sourceFile.getFilePath(), index);
     }
+    return index;
+  }
 
-    // Also search through project source files
-    for (const sf of this.project.getSourceFiles()) {
-      decl = sf.getInterface(typeName) || sf.getTypeAlias(typeName) || sf.getEnum(typeName);
-      if (decl) {
-        return decl;
+  /**
+   * The types visible in a file's scope by name, following imports to their declarations.
+   * TypeScript lib types are left out.
+   */
+  private getScopeDeclarations(sourceFile: SourceFile): Map<string, TypeDeclaration> {
+    let index = this.scopeIndex.get(sourceFile.getFilePath());
+    if (!index) {
+      index = new Map();
+      const symbols = this.project
+        .getTypeChecker()
+        .getSymbolsInScope(sourceFile, ts.SymbolFlags.Type | ts.SymbolFlags.Alias);
+      for (const symbol of symbols) {
+        const target = symbol.isAlias() ? symbol.getAliasedSymbol() : symbol;
+        const declaration = target
+          ?.getDeclarations()
+          .find((d): d is TypeDeclaration => isTypeDeclaration(d) && !this.isFromTypeScriptLib(d.getSourceFile().getFilePath()));
+        if (declaration && !index.has(symbol.getName())) {
+          index.set(symbol.getName(), declaration);
+        }
       }
+      this.scopeIndex.set(sourceFile.getFilePath(), index);
     }
+    return index;
+  }
 
-    return undefined;
+  /**
+   * Find a type by its qualified name through namespaces and namespace re-exports (`export * as models from`)
+   */
+  private findNamespaceMember(sourceFile: SourceFile, path: string[]): TypeDeclaration | undefined {
+    const typeName = path[path.length - 1]!;
+    let container: SourceFile | ModuleDeclaration = sourceFile;
+    for (const namespaceName of path.slice(0, -1)) {
+      const namespace: SourceFile | ModuleDeclaration | undefined =
+        container.getModule(namespaceName) ??
+        (Node.isSourceFile(container)
+          ? this.getExportedDeclarations(container).get(namespaceName)?.find(
+            (d): d is SourceFile | ModuleDeclaration => Node.isSourceFile(d) || Node.isModuleDeclaration(d),
+          )
+          : undefined);
+      if (!namespace) {
+        return undefined;
+      }
+      container = namespace;
+    }
+    if (Node.isSourceFile(container)) {
+      return this.getFileDeclarations(container).get(typeName) ??
+        this.getExportedDeclarations(container).get(typeName)?.find(isTypeDeclaration);
+    }
+    return (
+      container.getInterface(typeName) ||
+      container.getTypeAlias(typeName) ||
+      container.getEnum(typeName) ||
+      container.getClass(typeName)
+    );
   }
 
   private resolveInterface(declaration: InterfaceDeclaration): void {
-    const name = declaration.getName();
+    const name = this.getTypeKey(declaration);
     const fields: StructField[] = [];
     const typeParams = declaration.getTypeParameters().map((p) => p.getName());
 
@@ -285,25 +1220,64 @@ export class TypeResolver {
     typeParams.forEach((tp) => this.typeParameters.add(tp));
 
     try {
-      for (const extendedType of declaration.getExtends()) {
-        const baseType = extendedType.getType();
-        const baseFields = this.extractFieldsFromType(baseType);
-        fields.push(...baseFields);
+      // Interfaces can be declared several times (declaration merging) or augmented
+      // through `declare module "..."`, so every merged declaration contributes fields
+      const declarations = this.getMergedInterfaceDeclarations(declaration);
+
+      // Merged declarations may extend the same base, whose fields are only added once
+      const baseTypes = new Map<string, Type>();
+      for (const decl of declarations) {
+        for (const extendedType of decl.getExtends()) {
+          const baseType = extendedType.getType();
+          if (!baseTypes.has(baseType.getText())) {
+            baseTypes.set(baseType.getText(), baseType);
+          }
+        }
+      }
+      const baseFields = new Map<string, StructField>();
+      for (const baseType of baseTypes.values()) {
+        for (const field of this.extractFieldsFromType(baseType, name)) {
+          if (!baseFields.has(field.name)) {
+            baseFields.set(field.name, field);
+          }
+        }
       }
 
-      for (const prop of declaration.getProperties()) {
-        fields.push(this.resolveProperty(prop));
+      const ownFields = new Map<string, { field: StructField; decl: InterfaceDeclaration }>();
+      const declaredFields: StructField[] = [];
+      for (const decl of declarations) {
+        for (const prop of decl.getProperties()) {
+          const field = this.resolveProperty(prop, name);
+          const existing = ownFields.get(field.name);
+          if (!existing) {
+            ownFields.set(field.name, { field, decl });
+            declaredFields.push(field);
+          } else if (
+            existing.field.optional !== field.optional ||
+            JSON.stringify(existing.field.type) !== JSON.stringify(field.type)
+          ) {
+            this.fail(
+              "conflicting-declaration",
+              `Conflicting declarations of property '${field.name}' in merged interface (also declared in ${existing.decl.getSourceFile().getFilePath()}:${existing.decl.getStartLineNumber()})`,
+              { node: prop },
+            );
+          }
+        }
       }
+      // Properties redeclared by the interface (e.g. narrowed) take the place of the base's
+      fields.push(...[...baseFields.values()].filter((field) => !ownFields.has(field.name)), ...declaredFields);
+
+      this.lintDroppedMethods(name, declarations.flatMap((decl) => decl.getMethods()));
 
       const structType: StructType = {
         kind: "struct",
         name,
         fields,
-        documentation: this.getDocumentation(declaration),
+        documentation: declarations.map((d) => this.getDocumentation(d)).find((doc) => doc),
         typeParameters: typeParams.length > 0 ? typeParams : undefined,
       };
 
-      this.collectedTypes.set(name, {
+      this.collect(name, {
         name,
         type: structType,
         sourceFile: declaration.getSourceFile().getFilePath(),
@@ -313,39 +1287,56 @@ export class TypeResolver {
     }
   }
 
-  private extractFieldsFromType(type: Type): StructField[] {
+  /**
+   * Get all declarations merged into the interface's symbol, starting with `declaration` itself.
+   * This includes re-declarations in the same scope and module augmentations in other files.
+   */
+  private getMergedInterfaceDeclarations(declaration: InterfaceDeclaration): InterfaceDeclaration[] {
+    const merged = declaration.getType().getSymbol()?.getDeclarations() ?? [];
+    return [
+      declaration,
+      ...merged.filter((d): d is InterfaceDeclaration => Node.isInterfaceDeclaration(d) && d !== declaration),
+    ];
+  }
+
+  private extractFieldsFromType(type: Type, ownerName: string): StructField[] {
     const fields: StructField[] = [];
     const properties = type.getProperties();
 
     for (const prop of properties) {
       const propDecl = prop.getDeclarations()[0];
       if (propDecl && Node.isPropertySignature(propDecl)) {
-        fields.push(this.resolveProperty(propDecl));
+        fields.push(this.resolveProperty(propDecl, ownerName));
+      } else if (propDecl && this.isClassDataMember(propDecl)) {
+        const field = this.resolveProperty(propDecl, ownerName);
+        const defaultValue = this.getInitializerDefault(propDecl, field.type);
+        fields.push(defaultValue ? { ...field, defaultValue } : field);
       }
     }
 
     return fields;
   }
 
-  private resolveProperty(prop: PropertySignature): StructField {
+  private resolveProperty(
+    prop: PropertySignature | PropertyDeclaration | ParameterDeclaration,
+    ownerName: string,
+  ): StructField {
     const name = prop.getName();
     const isOptional = prop.hasQuestionToken();
     const sourceFile = prop.getSourceFile();
 
     const typeNode = prop.getTypeNode();
-    let resolvedType: ResolvedType;
-    
-    if (typeNode && typeNode.getKind() === SyntaxKind.TypeReference) {
-      // Use the type node for TypeReference to preserve alias names
-      resolvedType = this.resolveTypeFromNode(typeNode, sourceFile);
-    } else if (typeNode) {
-      const type = typeNode.getType();
-      resolvedType = this.resolveTypeWithNode(type, sourceFile, typeNode);
-    } else {
+    let resolvedType: ResolvedType = this.withFieldNameHint(ownerName, name, prop, () => {
+      if (typeNode && typeNode.getKind() === SyntaxKind.TypeReference) {
+        // Use the type node for TypeReference to preserve alias names
+        return this.resolveTypeFromNode(typeNode, sourceFile);
+      } else if (typeNode) {
+        const type = typeNode.getType();
+        return this.resolveTypeWithNode(type, sourceFile, typeNode);
+      }
       // Fallback to resolving from the Type object
-      const type = prop.getType();
-      resolvedType = this.resolveType(type, sourceFile);
-    }
+      return this.resolveType(prop.getType(), sourceFile);
+    });
 
     // Check for direct recursive reference that needs Box wrapping
     // A recursive reference needs Box if:
@@ -370,11 +1361,12 @@ export class TypeResolver {
       type: resolvedType,
       optional: isOptional,
       documentation: this.getDocumentation(prop),
+      ...this.getSource(prop),
     };
   }
 
   private resolveTypeAlias(declaration: TypeAliasDeclaration): void {
-    const name = declaration.getName();
+    const name = this.getTypeKey(declaration);
     const type = declaration.getType();
 
     // Track type parameters for this declaration (like resolveInterface does)
@@ -395,6 +1387,33 @@ export class TypeResolver {
     declaration: TypeAliasDeclaration,
     typeParams: string[],
   ): void {
+    // Generic conditional types can only be evaluated where they are used with concrete arguments
+    if (this.isDeferredConditionalType(type)) {
+      this.warn(
+        "deferred-conditional",
+        `Conditional type '${this.getKeyName(name)}' depends on its type parameters and is only converted where it is used with concrete arguments`,
+      );
+      return;
+    }
+
+    const enumObjectVariants = this.getEnumObjectVariants(declaration);
+    if (enumObjectVariants) {
+      const enumType: EnumType = {
+        kind: "enum",
+        name,
+        variants: enumObjectVariants,
+        isStringEnum: enumObjectVariants.some((v) => typeof v.value === "string"),
+        documentation: this.getDocumentation(declaration),
+      };
+
+      this.collect(name, {
+        name,
+        type: enumType,
+        sourceFile: declaration.getSourceFile().getFilePath(),
+      });
+      return;
+    }
+
     // Check for tuple types first (before object check, since tuples are objects)
     if (type.isTuple()) {
       const tupleTypes = type.getTupleElements();
@@ -408,7 +1427,7 @@ export class TypeResolver {
         documentation: this.getDocumentation(declaration),
       };
       
-      this.collectedTypes.set(name, {
+      this.collect(name, {
         name,
         type: aliasType,
         sourceFile: declaration.getSourceFile().getFilePath(),
@@ -418,6 +1437,22 @@ export class TypeResolver {
 
     // Check if it's an object type that should become a struct
     if (type.isObject() && !type.isArray() && !this.isBuiltInType(type)) {
+      if (this.isLiteralKeyMappedType(type)) {
+        const structType: StructType = {
+          ...this.resolveMappedTypeFields(type, declaration.getSourceFile(), name),
+          name,
+          documentation: this.getDocumentation(declaration),
+          typeParameters: typeParams.length > 0 ? typeParams : undefined,
+        };
+
+        this.collect(name, {
+          name,
+          type: structType,
+          sourceFile: declaration.getSourceFile().getFilePath(),
+        });
+        return;
+      }
+
       const properties = type.getProperties();
       if (properties.length > 0) {
         const fields: StructField[] = [];
@@ -440,7 +1475,9 @@ export class TypeResolver {
             propType = prop.getTypeAtLocation(declaration);
           }
 
-          let resolvedType = this.resolveTypeWithNode(propType, declaration.getSourceFile(), typeNode);
+          let resolvedType = this.withFieldNameHint(name, prop.getName(), propDecl, () =>
+            this.resolveTypeWithNode(propType, declaration.getSourceFile(), typeNode),
+          );
 
           if (isOptional) {
             resolvedType = {
@@ -454,6 +1491,7 @@ export class TypeResolver {
             type: resolvedType,
             optional: isOptional,
             documentation,
+            ...this.getSource(propDecl),
           });
         }
 
@@ -465,7 +1503,7 @@ export class TypeResolver {
           typeParameters: typeParams.length > 0 ? typeParams : undefined,
         };
 
-        this.collectedTypes.set(name, {
+        this.collect(name, {
           name,
           type: structType,
           sourceFile: declaration.getSourceFile().getFilePath(),
@@ -474,6 +1512,24 @@ export class TypeResolver {
       }
     }
 
+    const templatePatterns = this.getTemplateLiteralPatterns(type);
+    if (templatePatterns) {
+      const aliasType: TypeAliasType = {
+        kind: "type_alias",
+        name,
+        aliasedType: { kind: "primitive", type: "string" },
+        documentation: this.getDocumentation(declaration),
+        templatePatterns,
+      };
+
+      this.collect(name, {
+        name,
+        type: aliasType,
+        sourceFile: declaration.getSourceFile().getFilePath(),
+      });
+      return;
+    }
+
     if (type.isUnion()) {
       const unionTypes = type.getUnionTypes();
       
@@ -481,23 +1537,20 @@ export class TypeResolver {
         const unionType = this.resolveDiscriminatedUnion(name, unionTypes, declaration);
 
         if (unionType === null) {
-          this.warnings.push(
-            `Discriminated union type '${name}' has unresolvable variants and will be used as serde_json::Value in other types (at ${declaration.getSourceFile().getFilePath()})`
+          this.warn(
+            "unresolvable-union",
+            `Discriminated union type '${this.getKeyName(name)}' has unresolvable variants and will be used as serde_json::Value in other types`,
           );
           return;
         }
         
-        this.collectedTypes.set(name, {
-          name,
-          type: unionType,
-          sourceFile: declaration.getSourceFile().getFilePath(),
-        });
+        this.collectUnion(name, unionType, declaration);
         return;
       }
 
       if (this.isLiteralUnion(unionTypes)) {
         const enumType = this.resolveLiteralUnionAsEnum(name, unionTypes, declaration);
-        this.collectedTypes.set(name, {
+        this.collect(name, {
           name,
           type: enumType,
           sourceFile: declaration.getSourceFile().getFilePath(),
@@ -510,17 +1563,14 @@ export class TypeResolver {
       // If the union has unresolvable types, don't collect it
       // It will be used as Value in other types
       if (unionType === null) {
-        this.warnings.push(
-          `Union type '${name}' has unresolvable variants and will be used as serde_json::Value in other types (at ${declaration.getSourceFile().getFilePath()})`
+        this.warn(
+          "unresolvable-union",
+          `Union type '${this.getKeyName(name)}' has unresolvable variants and will be used as serde_json::Value in other types`,
         );
         return;
       }
       
-      this.collectedTypes.set(name, {
-        name,
-        type: unionType,
-        sourceFile: declaration.getSourceFile().getFilePath(),
-      });
+      this.collectUnion(name, unionType, declaration);
       return;
     }
 
@@ -528,7 +1578,7 @@ export class TypeResolver {
     
     // If the resolved type is a struct/enum with a different name, create a type alias struct
     if (resolvedType.kind === "struct" || resolvedType.kind === "enum" || resolvedType.kind === "union") {
-      this.collectedTypes.set(name, {
+      this.collect(name, {
         name,
         type: resolvedType as StructType | EnumType | UnionType,
         sourceFile: declaration.getSourceFile().getFilePath(),
@@ -536,8 +1586,28 @@ export class TypeResolver {
     }
   }
 
+  /**
+   * Collect a union type alias, reporting the `untagged-union` lint unless its variants are told apart by a string
+   * discriminator (see `generateUnion`)
+   */
+  private collectUnion(name: string, unionType: UnionType, declaration: TypeAliasDeclaration): void {
+    const tagged = unionType.discriminator !== undefined &&
+      unionType.variants.every((variant) => typeof variant.discriminatorValue === "string");
+    if (!tagged) {
+      this.lint(
+        "untagged-union",
+        `Union type '${this.getKeyName(name)}' is converted to an untagged enum, deserialized as the first variant that matches`,
+      );
+    }
+    this.collect(name, {
+      name,
+      type: unionType,
+      sourceFile: declaration.getSourceFile().getFilePath(),
+    });
+  }
+
   private resolveEnum(declaration: EnumDeclaration): void {
-    const name = declaration.getName();
+    const name = this.getTypeKey(declaration);
     const members = declaration.getMembers();
     const variants: EnumVariant[] = [];
     let isStringEnum = false;
@@ -554,6 +1624,7 @@ export class TypeResolver {
         name: memberName,
         value: value,
         documentation: this.getDocumentation(member),
+        ...this.getSource(member),
       });
     }
 
@@ -565,13 +1636,319 @@ export class TypeResolver {
       documentation: this.getDocumentation(declaration),
     };
 
-    this.collectedTypes.set(name, {
+    this.collect(name, {
       name,
       type: enumType,
       sourceFile: declaration.getSourceFile().getFilePath(),
     });
   }
 
+  /**
+   * Get the variants of an `as const` enum-object alias (`type Role = typeof Role[keyof typeof Role]`).
+   * Variant names come from the object keys and the serialized values from the object values.
+   */
+  private getEnumObjectVariants(declaration: TypeAliasDeclaration): EnumVariant[] | undefined {
+    const typeNode = this.unwrapParenthesizedType(declaration.getTypeNode());
+    if (!typeNode || !Node.isIndexedAccessTypeNode(typeNode)) {
+      return undefined;
+    }
+
+    const objectType = this.unwrapParenthesizedType(typeNode.getObjectTypeNode());
+    const indexType = this.unwrapParenthesizedType(typeNode.getIndexTypeNode());
+    if (
+      !objectType || !Node.isTypeQuery(objectType) ||
+      !indexType || !Node.isTypeOperatorTypeNode(indexType) ||
+      indexType.getOperator() !== SyntaxKind.KeyOfKeyword
+    ) {
+      return undefined;
+    }
+    const keyofTarget = this.unwrapParenthesizedType(indexType.getTypeNode());
+    const exprName = objectType.getExprName();
+    if (!keyofTarget || !Node.isTypeQuery(keyofTarget) || keyofTarget.getExprName().getText() !== exprName.getText()) {
+      return undefined;
+    }
+
+    const symbol = exprName.getSymbol();
+    const variable = (symbol?.isAlias() ? symbol.getAliasedSymbol() : symbol)
+      ?.getDeclarations()
+      .find((d): d is VariableDeclaration => Node.isVariableDeclaration(d));
+    const initializer = variable?.getInitializer();
+    if (!initializer || !Node.isAsExpression(initializer) || initializer.getTypeNode()?.getText() !== "const") {
+      return undefined;
+    }
+
+    const value = this.getConstantValue(initializer);
+    if (value?.kind !== "object") {
+      return undefined;
+    }
+
+    const variants: EnumVariant[] = [];
+    const names = new Set<string>();
+    for (const [key, entry] of value.entries) {
+      if (entry.kind !== "literal" || typeof entry.value === "boolean") {
+        return undefined;
+      }
+      // Keys may be numeric (`404`) or collide once converted (`FOO_BAR` and `fooBar`), the value keeps the wire format
+      const converted = /^[A-Z0-9_]+$/.test(key) ? this.toVariantName(key) : this.toPascalCase(key);
+      const base = /^[A-Za-z]/.test(converted) ? converted : `Value${converted}`;
+      let name = base;
+      for (let n = 2; names.has(name); n++) {
+        name = `${base}${n}`;
+      }
+      names.add(name);
+      variants.push({ name, value: entry.value });
+    }
+    return variants;
+  }
+
+  private unwrapParenthesizedType(node: Node | undefined): Node | undefined {
+    while (node && Node.isParenthesizedTypeNode(node)) {
+      node = node.getTypeNode();
+    }
+    return node;
+  }
+
+  /**
+   * Resolve an exported `const` holding literal data (strings, numbers, booleans, arrays and objects of them).
+   * When the constant was requested explicitly, a non-literal initializer is an error, otherwise it is skipped.
+   */
+  private resolveConstant(declaration: VariableDeclaration, explicit: boolean): string {
+    const name = `${declaration.getSourceFile().getFilePath()}#${declaration.getName()}`;
+    const statement = declaration.getVariableStatement();
+    const initializer = declaration.getInitializer();
+    const value = initializer ? this.getConstantValue(initializer) : undefined;
+
+    if (!statement || statement.getDeclarationKind() !== VariableDeclarationKind.Const || !value || value.kind === "null") {
+      if (explicit) {
+        this.fail("invalid-constant", "Only `const` declarations initialized with literal data can be converted", {
+          node: declaration,
+          typeName: declaration.getName(),
+        });
+      }
+      return name;
+    }
+
+    this.collect(name, {
+      name,
+      type: {
+        kind: "constant",
+        name,
+        value,
+        documentation: this.getDocumentation(statement),
+      },
+      sourceFile: declaration.getSourceFile().getFilePath(),
+      line: declaration.getStartLineNumber(),
+    });
+    return name;
+  }
+
+  /**
+   * Read literal data from an expression, looking through `as const`, `satisfies` and parentheses.
+   * Returns undefined for anything computed (identifiers, calls, spreads, template substitutions, ...).
+   */
+  private getConstantValue(node: Node): ConstantValue | undefined {
+    while (Node.isAsExpression(node) || Node.isSatisfiesExpression(node) || Node.isParenthesizedExpression(node)) {
+      node = node.getExpression();
+    }
+
+    if (Node.isStringLiteral(node) || Node.isNoSubstitutionTemplateLiteral(node)) {
+      return { kind: "literal", value: node.getLiteralValue() };
+    }
+    if (Node.isNumericLiteral(node)) {
+      return { kind: "literal", value: node.getLiteralValue() };
+    }
+    if (
+      Node.isPrefixUnaryExpression(node) &&
+      node.getOperatorToken() === SyntaxKind.MinusToken &&
+      Node.isNumericLiteral(node.getOperand())
+    ) {
+      return { kind: "literal", value: -node.getOperand().asKindOrThrow(SyntaxKind.NumericLiteral).getLiteralValue() };
+    }
+    if (Node.isTrueLiteral(node) || Node.isFalseLiteral(node)) {
+      return { kind: "literal", value: node.getLiteralValue() };
+    }
+    if (Node.isNullLiteral(node)) {
+      return { kind: "null" };
+    }
+
+    if (Node.isArrayLiteralExpression(node)) {
+      const elements: ConstantValue[] = [];
+      for (const element of node.getElements()) {
+        const value = this.getConstantValue(element);
+        if (!value) {
+          return undefined;
+        }
+        elements.push(value);
+      }
+      return { kind: "array", elements };
+    }
+
+    if (Node.isObjectLiteralExpression(node)) {
+      const entries: [string, ConstantValue][] = [];
+      for (const property of node.getProperties()) {
+        if (!Node.isPropertyAssignment(property)) {
+          return undefined;
+        }
+        const nameNode = property.getNameNode();
+        let key: string;
+        if (Node.isIdentifier(nameNode) || Node.isNumericLiteral(nameNode)) {
+          key = nameNode.getText();
+        } else if (Node.isStringLiteral(nameNode) || Node.isNoSubstitutionTemplateLiteral(nameNode)) {
+          key = nameNode.getLiteralValue();
+        } else {
+          return undefined;
+        }
+        const value = this.getConstantValue(property.getInitializerOrThrow());
+        if (!value) {
+          return undefined;
+        }
+        entries.push([key, value]);
+      }
+      return { kind: "object", entries };
+    }
+
+    return undefined;
+  }
+
+  /**
+   * Resolve a class to a struct built from its public instance properties (including constructor
+   * parameter properties). Property initializers become serde defaults.
+   */
+  private resolveClass(declaration: ClassDeclaration): void {
+    if (!declaration.getName()) {
+      return;
+    }
+    const name = this.getTypeKey(declaration);
+
+    const fields: StructField[] = [];
+    const typeParams = declaration.getTypeParameters().map((p) => p.getName());
+
+    const previousTypeParams = new Set(this.typeParameters);
+    typeParams.forEach((tp) => this.typeParameters.add(tp));
+
+    try {
+      const members = [
+        ...declaration.getConstructors().flatMap((c) => c.getParameters()),
+        ...declaration.getProperties(),
+      ].sort((a, b) => a.getStart() - b.getStart());
+      const declaredFields: StructField[] = [];
+      for (const member of members) {
+        if (!this.isClassDataMember(member)) {
+          continue;
+        }
+        const field = this.resolveProperty(member, name);
+        const defaultValue = this.getInitializerDefault(member, field.type);
+        declaredFields.push(defaultValue ? { ...field, defaultValue } : field);
+      }
+
+      // Properties redeclared by the class (e.g. `override role: "admin"`) take the place of the base's
+      const baseClass = declaration.getExtends();
+      if (baseClass) {
+        const ownNames = new Set(declaredFields.map((field) => field.name));
+        fields.push(
+          ...this.extractFieldsFromType(baseClass.getType(), name).filter((field) => !ownNames.has(field.name)),
+        );
+      }
+      fields.push(...declaredFields);
+      this.lintDroppedMethods(name, declaration.getMethods().filter((method) => !method.isStatic()));
+
+      const structType: StructType = {
+        kind: "struct",
+        name,
+        fields,
+        documentation: this.getDocumentation(declaration),
+        typeParameters: typeParams.length > 0 ? typeParams : undefined,
+      };
+
+      this.collect(name, {
+        name,
+        type: structType,
+        sourceFile: declaration.getSourceFile().getFilePath(),
+      });
+    } finally {
+      this.typeParameters = previousTypeParams;
+    }
+  }
+
+  /**
+   * Report the `dropped-methods` lint for the methods of an interface or class, which have no Rust counterpart
+   */
+  private lintDroppedMethods(name: string, methods: { getName(): string }[]): void {
+    if (methods.length > 0) {
+      const names = [...new Set(methods.map((method) => method.getName()))];
+      this.lint("dropped-methods", `Methods of '${this.getKeyName(name)}' are not converted: ${names.join(", ")}`);
+    }
+  }
+
+  /**
+   * Check if a class member is serialized data: an instance property (or constructor parameter property)
+   * with a visible scope that doesn't hold a function. Methods and accessors are never included.
+   */
+  private isClassDataMember(node: Node): node is PropertyDeclaration | ParameterDeclaration {
+    if (Node.isPropertyDeclaration(node)) {
+      if (node.isStatic() || Node.isPrivateIdentifier(node.getNameNode())) {
+        return false;
+      }
+    } else if (!Node.isParameterDeclaration(node) || !node.isParameterProperty()) {
+      return false;
+    }
+
+    const visibility = this.options.classMemberVisibility ?? "public";
+    const scope = node.getScope();
+    if (scope === Scope.Private && visibility !== "all") {
+      return false;
+    }
+    if (scope === Scope.Protected && visibility === "public") {
+      return false;
+    }
+
+    return node.getType().getCallSignatures().length === 0;
+  }
+
+  /**
+   * Get the serde default for a class property from its initializer (`name = ""`, `tags: string[] = []`).
+   * Only literals matching the resolved field type and empty collections/`null` are supported.
+   */
+  private getInitializerDefault(
+    member: PropertyDeclaration | ParameterDeclaration,
+    fieldType: ResolvedType,
+  ): StructField["defaultValue"] {
+    const initializer = member.getInitializer();
+    if (!initializer) {
+      return undefined;
+    }
+
+    if (initializer.getKind() === SyntaxKind.NullKeyword) {
+      // `Default` is only known to be `None` for options, other types have no default
+      return fieldType.kind === "option" ? { kind: "default" } : undefined;
+    }
+    if (
+      (Node.isArrayLiteralExpression(initializer) && initializer.getElements().length === 0) ||
+      (Node.isObjectLiteralExpression(initializer) && initializer.getProperties().length === 0)
+    ) {
+      // Generated structs don't implement `Default`, only collections and options have an empty default
+      const defaultKinds = ["array", "set", "record", "map", "option"];
+      return defaultKinds.includes(fieldType.kind) ? { kind: "default" } : undefined;
+    }
+
+    const valueType = fieldType.kind === "option" ? fieldType.innerType : fieldType;
+    if (valueType.kind !== "primitive") {
+      return undefined;
+    }
+
+    const literal = initializer.getType();
+    if (valueType.type === "string" && literal.isStringLiteral()) {
+      return { kind: "literal", value: literal.getLiteralValue() as string };
+    }
+    if (valueType.type === "number" && literal.isNumberLiteral()) {
+      return { kind: "literal", value: literal.getLiteralValue() as number };
+    }
+    if (valueType.type === "boolean" && literal.isBooleanLiteral()) {
+      return { kind: "literal", value: literal.getText() === "true" };
+    }
+    return undefined;
+  }
+
   /**
    * Resolve a type with optional type node for better accuracy.
    * The type node preserves the original syntax which helps with unions like `TypeAlias | null`
@@ -606,18 +1983,21 @@ export class TypeResolver {
                 };
               }
 
-              const declaration = this.findTypeDeclaration(sourceFile, typeName);
-              if (declaration) {
-                this.resolveTypeByName(sourceFile, typeName);
+              // References to namespace members are resolved through the type checker below
+              const declaration = this.getNamespacedDeclaration(typeRef.getTypeName())
+                ? undefined
+                : this.getReferencedDeclaration(typeRef.getTypeName()) ?? this.findTypeDeclaration(sourceFile, typeName);
+              if (declaration && !this.isConditionalTypeAlias(declaration)) {
+                const key = this.resolveDeclaration(declaration);
                 // Only resolve type arguments if the type was actually collected
-                if (this.collectedTypes.has(typeName) || this.processingTypes.has(typeName)) {
+                if (this.isResolved(key)) {
                   const innerTypeArgsNodes = typeRef.getTypeArguments();
                   const innerTypeArguments: ResolvedType[] | undefined = innerTypeArgsNodes.length > 0
                     ? innerTypeArgsNodes.map((argNode) => this.resolveTypeFromNode(argNode, sourceFile))
                     : undefined;
                   // Check if this is a recursive reference that needs Box wrapping
-                  let innerType: ResolvedType = { kind: "struct", name: typeName, fields: [], typeArguments: innerTypeArguments };
-                  if (this.processingTypes.has(typeName)) {
+                  let innerType: ResolvedType = { kind: "struct", name: key, fields: [], typeArguments: innerTypeArguments };
+                  if (this.processingTypes.has(key)) {
                     innerType = { kind: "box", innerType };
                   }
                   return {
@@ -677,6 +2057,27 @@ export class TypeResolver {
       if (typeRef) {
         const typeNameNode = typeRef.getTypeName();
         const typeName = typeNameNode.getText();
+
+        const namespacedDecl = this.getNamespacedDeclaration(typeNameNode);
+        if (
+          namespacedDecl &&
+          !this.isFromNodeModules(namespacedDecl.getSourceFile().getFilePath()) &&
+          !this.isConditionalTypeAlias(namespacedDecl)
+        ) {
+          const name = this.resolveDeclaration(namespacedDecl);
+          if (this.isResolved(name)) {
+            const typeArgsNodes = typeRef.getTypeArguments();
+            const typeArguments: ResolvedType[] | undefined = typeArgsNodes.length > 0
+              ? typeArgsNodes.map((argNode) => this.resolveTypeFromNode(argNode, sourceFile))
+              : undefined;
+            return { kind: "struct", name, fields: [], typeArguments };
+          }
+          return this.handleValueFallback(
+            `Type '${this.getKeyName(name)}' could not be fully resolved`,
+            undefined,
+            sourceFile.getFilePath(),
+          );
+        }
         
         // Handle qualified names (like PackageJson.WorkspaceConfig) by falling through
         // to the type checker, which has the logic to handle nested types
@@ -696,19 +2097,23 @@ export class TypeResolver {
           return this.resolveType(type, sourceFile);
         }
         
-        // Try to resolve as a local type
-        const declaration = this.findTypeDeclaration(sourceFile, typeName);
+        // Try to resolve as a local or imported type
+        const declaration = this.getReferencedDeclaration(typeNameNode) ?? this.findTypeDeclaration(sourceFile, typeName);
+        if (declaration && this.isConditionalTypeAlias(declaration) && typeRef.getTypeArguments().length > 0) {
+          // `Id<User>` where `type Id<T> = T extends User ? UserId : string` - use the checker's evaluated type
+          return this.resolveType(typeNode.getType(), sourceFile);
+        }
         if (declaration) {
-          this.resolveTypeByName(sourceFile, typeName);
+          const key = this.resolveDeclaration(declaration);
           // Check if the type was actually collected (it might not be if it has unresolvable variants)
-          if (this.collectedTypes.has(typeName) || this.processingTypes.has(typeName)) {
+          if (this.isResolved(key)) {
             // Only resolve type arguments after confirming the parent type resolved successfully,
             // to avoid collecting unused types (e.g., Error in Result<string, Error> when Result fails)
             const typeArgsNodes = typeRef.getTypeArguments();
             const typeArguments: ResolvedType[] | undefined = typeArgsNodes.length > 0
               ? typeArgsNodes.map((argNode) => this.resolveTypeFromNode(argNode, sourceFile))
               : undefined;
-            return { kind: "struct", name: typeName, fields: [], typeArguments };
+            return { kind: "struct", name: key, fields: [], typeArguments };
           }
           // Type wasn't collected - fall back to Value
           return this.handleValueFallback(
@@ -754,10 +2159,10 @@ export class TypeResolver {
 
             const declaration = this.findTypeDeclaration(moduleSourceFile, typeName);
             if (declaration) {
-              this.resolveTypeByName(moduleSourceFile, typeName);
+              const key = this.resolveDeclaration(declaration);
               
               // Check if the type was actually collected
-              if (!this.collectedTypes.has(typeName)) {
+              if (!this.collectedTypes.has(key) && !this.externalTypes.has(key) && !this.excludedTypes.has(key)) {
                 return this.handleValueFallback(
                   `Type '${typeName}' could not be fully resolved`,
                   undefined,
@@ -765,7 +2170,7 @@ export class TypeResolver {
                 );
               }
               
-              return { kind: "struct", name: typeName, fields: [] };
+              return { kind: "struct", name: key, fields: [] };
             }
           }
           // Found the import but couldn't resolve it - fall through to other resolution
@@ -813,8 +2218,20 @@ export class TypeResolver {
     // Check for type aliases - if the type is an alias to something,
     // we should resolve the alias, not the underlying type
     // But skip for built-in aliases
+    if (this.isDeferredConditionalType(type)) {
+      return this.handleValueFallback(
+        "Conditional type cannot be evaluated without concrete type arguments",
+        type,
+        sourceFile.getFilePath(),
+      );
+    }
+
     const aliasSymbol = type.getAliasSymbol();
-    if (aliasSymbol && !this.isBuiltInAlias(aliasSymbol.getName())) {
+    if (
+      aliasSymbol &&
+      !this.isBuiltInAlias(aliasSymbol.getName()) &&
+      !aliasSymbol.getDeclarations().some((d) => this.isConditionalTypeAlias(d))
+    ) {
       const aliasName = aliasSymbol.getName();
       
       // If it's a type parameter we're tracking, preserve it
@@ -830,18 +2247,22 @@ export class TypeResolver {
         if (!this.project.getSourceFile(declFilePath)) {
           this.project.addSourceFileAtPath(declFilePath);
         }
+
+        const namespacedDecl = this.findNamespacedDeclaration([decl]);
+        if (namespacedDecl && !this.isFromNodeModules(declFilePath)) {
+          return this.resolveNamespacedReference(namespacedDecl, type, sourceFile);
+        }
         
         // Check if this is a top-level type or a nested type (e.g., in a namespace)
-        // Nested types will fail findTypeDeclaration, so we should skip resolveTypeByName
-        // and let the later code handle them
-        const typeDecl = this.findTypeDeclaration(declSourceFile, aliasName);
+        // Nested node_modules types are left to the later code
+        const typeDecl = isTypeDeclaration(decl) && this.getNamespacePath(decl).length === 0 ? decl : undefined;
         if (typeDecl) {
-          this.resolveTypeByName(declSourceFile, aliasName);
+          const key = this.resolveDeclaration(typeDecl);
           
-          if (this.collectedTypes.has(aliasName) || this.processingTypes.has(aliasName)) {
+          if (this.isResolved(key)) {
             return {
               kind: "struct",
-              name: aliasName,
+              name: key,
               fields: [],
             };
           }
@@ -871,7 +2292,15 @@ export class TypeResolver {
       return { kind: "primitive", type: "string" };
     }
 
+    // Template literal (`` `usr-${number}` ``) and intrinsic string mapping (`Uppercase<string>`) types
+    if (type.getFlags() & (ts.TypeFlags.TemplateLiteral | ts.TypeFlags.StringMapping)) {
+      return { kind: "primitive", type: "string" };
+    }
+
     if (type.isNumber() || type.isNumberLiteral()) {
+      if (type.isNumberLiteral()) {
+        this.lint("lossy-number", `Number literal type ${type.getText()} is converted to f64, which accepts any number`);
+      }
       return { kind: "primitive", type: "number" };
     }
 
@@ -965,19 +2394,19 @@ export class TypeResolver {
             const typeDecl = this.findTypeDeclaration(declSourceFile, aliasName);
             
             if (typeDecl) {
-              this.resolveTypeByName(declSourceFile, aliasName);
+              const key = this.resolveDeclaration(typeDecl);
               
               return {
                 kind: "struct",
-                name: aliasName,
+                name: key,
                 fields: [],
               };
             } else {
               // It's a nested type (like PackageJson.WorkspaceConfig)
-              // Create a unique name and collect it as a named type
-              const uniqueName = aliasName;
+              // Collect it under its namespace-qualified name so it is emitted in a module
+              const uniqueName = `${filePath}#${[...this.getNamespacePath(decl), aliasName].join(".")}`;
 
-              if (!this.collectedTypes.has(uniqueName)) {
+              if (!this.collectedTypes.has(uniqueName) && !this.isExternalType(uniqueName)) {
                 const fields: StructField[] = [];
 
                 for (const prop of properties) {
@@ -1002,6 +2431,7 @@ export class TypeResolver {
                     name: prop.getName(),
                     type: resolvedType,
                     optional: isOptional,
+                    ...this.getSource(propDecl),
                   });
                 }
 
@@ -1011,11 +2441,13 @@ export class TypeResolver {
                   fields,
                 };
 
-                this.collectedTypes.set(uniqueName, {
+                this.collect(uniqueName, {
                   name: uniqueName,
                   type: structType,
                   sourceFile: filePath,
                 });
+              } else {
+                this.recordCollected(uniqueName);
               }
 
               return {
@@ -1029,6 +2461,12 @@ export class TypeResolver {
       }
     }
 
+    // Mapped types over a finite set of literal keys (e.g. `Record<"a" | "b", T>`) become structs.
+    // Open-ended keys produce an index signature and were handled as records above.
+    if (this.isLiteralKeyMappedType(type)) {
+      return this.resolveInlineMappedType(type, sourceFile);
+    }
+
     // Handle type references (named types)
     const symbol = type.getSymbol() || type.getAliasSymbol();
     if (symbol) {
@@ -1081,11 +2519,8 @@ export class TypeResolver {
       }
 
       if (symbolName === "Promise") {
-        throw new TypeConversionError(
-          "Promise",
-          "Promise types cannot be serialized to JSON",
-          sourceFile.getFilePath(),
-        );
+        this.fail("unsupported-type", "Promise types cannot be serialized to JSON", { file: sourceFile.getFilePath() });
+        return { kind: "json_value" };
       }
 
       if (this.isInternalType(symbolName)) {
@@ -1109,16 +2544,29 @@ export class TypeResolver {
               this.project.addSourceFileAtPath(filePath);
             }
 
-            this.resolveTypeByName(declSourceFile, symbolName);
+            const namespacedDecl = this.findNamespacedDeclaration(symbol.getDeclarations());
+            if (namespacedDecl) {
+              return this.resolveNamespacedReference(namespacedDecl, type, sourceFile);
+            }
+
+            const key = isTypeDeclaration(decl)
+              ? this.resolveDeclaration(decl)
+              : this.resolveTypeByName(declSourceFile, symbolName);
 
             // Allow recursive references - if type is being processed, it will be collected later
-            if (!this.collectedTypes.has(symbolName) && !this.processingTypes.has(symbolName)) {
+            if (!this.isResolved(key)) {
               return this.handleValueFallback(
                 `Type '${symbolName}' could not be fully resolved`,
                 type,
                 sourceFile.getFilePath(),
               );
             }
+
+            return {
+              kind: "struct",
+              name: key,
+              fields: [],
+            };
           } else {
             // For TypeScript lib types, return json_value
             return this.handleValueFallback(
@@ -1166,6 +2614,7 @@ export class TypeResolver {
             name: prop.getName(),
             type: resolvedType,
             optional: isOptional,
+            ...this.getSource(propDecl),
           });
         }
 
@@ -1186,6 +2635,193 @@ export class TypeResolver {
     );
   }
 
+  /**
+   * Check if a declaration is a type alias to a conditional type (`type Id<T> = T extends U ? X : Y`)
+   */
+  private isConditionalTypeAlias(declaration: Node): boolean {
+    return Node.isTypeAliasDeclaration(declaration) &&
+      declaration.getTypeNode()?.getKind() === SyntaxKind.ConditionalType;
+  }
+
+  /**
+   * Check if a type is a conditional type the checker could not evaluate (it depends on type parameters)
+   */
+  private isDeferredConditionalType(type: Type): boolean {
+    return (type.getFlags() & ts.TypeFlags.Conditional) !== 0;
+  }
+
+  /**
+   * Get the patterns of a template literal type, or of a union of template literal and string literal types.
+   * Returns undefined for any other type.
+   */
+  private getTemplateLiteralPatterns(type: Type): TemplateLiteralPart[][] | undefined {
+    const members = type.isUnion() ? type.getUnionTypes() : [type];
+    if (!members.some((t) => t.getFlags() & ts.TypeFlags.TemplateLiteral)) {
+      return undefined;
+    }
+
+    const patterns: TemplateLiteralPart[][] = [];
+    for (const member of members) {
+      if (member.isStringLiteral()) {
+        patterns.push([{ kind: "text", value: member.getLiteralValue() as string }]);
+      } else if (member.getFlags() & ts.TypeFlags.TemplateLiteral) {
+        const templateType = member.compilerType as ts.TemplateLiteralType;
+        const parts: TemplateLiteralPart[] = [];
+        templateType.texts.forEach((text, i) => {
+          if (text) {
+            parts.push({ kind: "text", value: text });
+          }
+          const hole = templateType.types[i];
+          if (hole) {
+            parts.push(this.toTemplateLiteralHole(hole));
+          }
+        });
+        patterns.push(parts);
+      } else {
+        return undefined;
+      }
+    }
+    return patterns;
+  }
+
+  private toTemplateLiteralHole(hole: ts.Type): TemplateLiteralPart {
+    if (hole.flags & ts.TypeFlags.Number) return { kind: "number" };
+    if (hole.flags & ts.TypeFlags.BigInt) return { kind: "bigint" };
+    // `string`, intrinsic string mappings (`Uppercase<string>`) and anything else match any text
+    return { kind: "string" };
+  }
+
+  /**
+   * Check if a type is a mapped type over a finite set of literal keys (`{ [K in "a" | "b"]: V }`, `Record<"a" | "b", V>`).
+   * Homomorphic mapped types (`Partial<T>`, `Readonly<T>`) link their properties back to the original
+   * declarations and are left to the regular object handling.
+   */
+  private isLiteralKeyMappedType(type: Type): boolean {
+    if ((type.getObjectFlags() & ts.ObjectFlags.Mapped) === 0) {
+      return false;
+    }
+    const properties = type.getProperties();
+    return properties.length > 0 && properties.every((p) => p.getDeclarations().length === 0);
+  }
+
+  /**
+   * Resolve the fields of a mapped type whose keys are a finite set of literals.
+   * Each field keeps the `?`/`readonly` modifiers of the mapped type and is serialized under its exact key.
+   */
+  private resolveMappedTypeFields(type: Type, sourceFile: SourceFile, ownerName: string): StructType {
+    const mappedNode = type.getSymbol()?.getDeclarations()[0];
+    const readonlyToken = mappedNode && Node.isMappedTypeNode(mappedNode)
+      ? mappedNode.getReadonlyToken()
+      : undefined;
+    const isReadonly = readonlyToken !== undefined && readonlyToken.getKind() !== SyntaxKind.MinusToken;
+
+    const fields: StructField[] = [];
+    for (const prop of type.getProperties()) {
+      const isOptional = prop.hasFlags(ts.SymbolFlags.Optional);
+      let resolvedType = this.withFieldNameHint(ownerName, prop.getName(), undefined, () =>
+        this.resolveType(prop.getTypeAtLocation(sourceFile), sourceFile),
+      );
+
+      if (isOptional && resolvedType.kind !== "option") {
+        resolvedType = { kind: "option", innerType: resolvedType };
+      }
+
+      fields.push({
+        name: prop.getName(),
+        type: resolvedType,
+        optional: isOptional,
+        exactName: true,
+        readonly: isReadonly || undefined,
+      });
+    }
+
+    return { kind: "struct", name: "", fields };
+  }
+
+  /**
+   * Resolve a mapped type used inline (e.g. a `labels: Record<Locale, string>` field).
+   * The struct is named after the field it appears in; without one it stays anonymous.
+   */
+  private resolveInlineMappedType(type: Type, sourceFile: SourceFile): ResolvedType {
+    if (!this.fieldNameHint) {
+      return this.resolveMappedTypeFields(type, sourceFile, "");
+    }
+    const name = this.getInlineTypeKey(sourceFile, this.fieldNameHint);
+
+    if (!this.collectedTypes.has(name) && !this.processingTypes.has(name)) {
+      this.processingTypes.add(name);
+      try {
+        this.collect(name, {
+          name,
+          type: { ...this.resolveMappedTypeFields(type, sourceFile, name), name },
+          sourceFile: sourceFile.getFilePath(),
+        });
+      } finally {
+        this.processingTypes.delete(name);
+      }
+    } else {
+      this.recordCollected(name);
+    }
+
+    return { kind: "struct", name, fields: [] };
+  }
+
+  /**
+   * The key of the struct synthesized for the inline mapped type of a field, suffixed (`ConfigLabels2`) when the
+   * name is taken by a type declared in the file or synthesized for another field
+   */
+  private getInlineTypeKey(sourceFile: SourceFile, hint: { name: string; field: string }): string {
+    const existing = this.inlineTypeKeys.get(hint.field);
+    if (existing) {
+      return existing;
+    }
+    const takenKeys = new Set(this.inlineTypeKeys.values());
+    const declarations = this.getFileDeclarations(sourceFile);
+    let name = hint.name;
+    for (let n = 2; declarations.has(name) || takenKeys.has(`${sourceFile.getFilePath()}#${name}`); n++) {
+      name = `${hint.name}${n}`;
+    }
+    const key = `${sourceFile.getFilePath()}#${name}`;
+    this.inlineTypeKeys.set(hint.field, key);
+    return key;
+  }
+
+  /**
+   * Run `resolve` with the name an inline mapped type in `ownerName.fieldName` should be collected under, and the
+   * field (declared by `node`, if any) as the location of diagnostics
+   */
+  private withFieldNameHint<T>(ownerName: string, fieldName: string, node: Node | undefined, resolve: () => T): T {
+    const previousHint = this.fieldNameHint;
+    this.fieldNameHint = ownerName
+      ? { name: this.getKeyName(ownerName) + this.toPascalCase(fieldName), field: `${ownerName}.${fieldName}` }
+      : undefined;
+    try {
+      return this.withLocation(
+        {
+          node: node ?? this.location.node,
+          fieldPath: [...this.location.fieldPath, fieldName],
+          allowedLints: [...this.location.allowedLints, ...(node ? this.getAllowedLints(node) : [])],
+        },
+        resolve,
+      );
+    } finally {
+      this.fieldNameHint = previousHint;
+    }
+  }
+
+  /**
+   * Run `resolve` with the location of its diagnostics
+   */
+  private withLocation<T>(location: DiagnosticLocation, resolve: () => T): T {
+    const previousLocation = this.location;
+    this.location = location;
+    try {
+      return resolve();
+    } finally {
+      this.location = previousLocation;
+    }
+  }
+
   /**
    * Resolve inline union types (e.g., string | number | Type1 | Type2)
    * 
@@ -1218,10 +2854,17 @@ export class TypeResolver {
           // For generic types, we can't easily instantiate them in Rust
           // Fall through to handle as inline union
         } else {
-          // Non-generic type alias - resolve it by name
-          this.resolveTypeByName(declSourceFile, typeName);
+          const namespacedDecl = this.findNamespacedDeclaration([decl]);
+          if (namespacedDecl) {
+            return this.resolveNamespacedReference(namespacedDecl, type, sourceFile);
+          }
+
+          // Non-generic type alias - resolve its declaration
+          const key = isTypeDeclaration(decl)
+            ? this.resolveDeclaration(decl)
+            : this.resolveTypeByName(declSourceFile, typeName);
 
-          if (!this.collectedTypes.has(typeName)) {
+          if (!this.collectedTypes.has(key) && !this.externalTypes.has(key) && !this.excludedTypes.has(key)) {
             return this.handleValueFallback(
               `Type '${typeName}' could not be fully resolved`,
               type,
@@ -1231,7 +2874,7 @@ export class TypeResolver {
 
           return {
             kind: "struct",  // Will be a union after resolution
-            name: typeName,
+            name: key,
             fields: [],
           };
         }
@@ -1511,9 +3154,11 @@ export class TypeResolver {
           name: propName,
           type: resolvedType,
           optional: isOptional,
+          ...this.getSource(propDecl),
         });
       }
 
+      const source = this.getSource(t.getSymbol()?.getDeclarations()[0]);
       if (fields.length > 0) {
         variants.push({
           name: variantName,
@@ -1523,12 +3168,14 @@ export class TypeResolver {
             fields,
           },
           discriminatorValue: discriminantValue ? String(discriminantValue) : undefined,
+          ...source,
         });
       } else {
         variants.push({
           name: variantName,
           type: null,
           discriminatorValue: discriminantValue ? String(discriminantValue) : undefined,
+          ...source,
         });
       }
     }
@@ -1607,6 +3254,7 @@ export class TypeResolver {
       variants.push({
         name: variantName,
         type: resolvedType.kind === "primitive" && resolvedType.type === "null" ? null : resolvedType,
+        ...this.getSource(symbol?.getDeclarations()[0]),
       });
     }
 
@@ -1632,6 +3280,23 @@ export class TypeResolver {
       .join("");
   }
 
+  /**
+   * Convert an identifier to PascalCase, keeping the casing of inner characters (e.g. `darkMode` => `DarkMode`)
+   */
+  private toPascalCase(value: string): string {
+    return value
+      .split(/[^A-Za-z0-9]+/)
+      .map((part) => part.charAt(0).toUpperCase() + part.slice(1))
+      .join("");
+  }
+
+  /**
+   * The lints allowed by `@ts2rs-allow` on a declaration
+   */
+  private getAllowedLints(node: Node): LintName[] {
+    return Node.isJSDocable(node) ? getAllowedLints(node.getJsDocs().flatMap((doc) => doc.getTags())) : [];
+  }
+
   private getDocumentation(node: Node): string | undefined {
     const jsDocs = (node as any).getJsDocs?.();
     if (jsDocs && jsDocs.length > 0) {
@@ -1,4 +1,6 @@
 
+import { createDiagnostic, type DiagnosticDetails } from "./diagnostics";
+
 /**
  * Represents a resolved TypeScript type that can be converted to Rust
  */
@@ -17,7 +19,8 @@ export type ResolvedType =
   | LiteralType         // "literal" | 123 | true
   | JsonValueType       // serde_json::Value (fallback)
   | TypeAliasType       // type T = ...
-  | TypeParameterType;  // Generic type parameter (e.g., T, U)
+  | TypeParameterType   // Generic type parameter (e.g., T, U)
+  | ExternalType;       // Type from a package mapped to a Rust crate
 
 export interface PrimitiveType {
   kind: "primitive";
@@ -39,8 +42,18 @@ export interface TypeAliasType {
   name: string;
   aliasedType: ResolvedType;
   documentation?: string;
+  templatePatterns?: TemplateLiteralPart[][]; // Set when the alias is a (union of) template literal type(s)
 }
 
+/**
+ * A segment of a template literal type, e.g. `` `usr-${number}` `` is `[text("usr-"), number]`
+ */
+export type TemplateLiteralPart =
+  | { kind: "text"; value: string }
+  | { kind: "string" }
+  | { kind: "number" }
+  | { kind: "bigint" };
+
 export interface TupleType {
   kind: "tuple";
   elements: ResolvedType[];
@@ -78,6 +91,18 @@ export interface StructField {
   type: ResolvedType;
   optional: boolean;
   documentation?: string;
+  exactName?: boolean; // Always serialize under `name` (e.g. keys of a mapped type)
+  readonly?: boolean;
+  defaultValue?: LiteralType | { kind: "default" }; // From a class property initializer, `default` is `Default::default()`
+  source?: SourceLocation;
+}
+
+/**
+ * Where a field or variant is declared
+ */
+export interface SourceLocation {
+  file: string;
+  line: number; // 1-based
 }
 
 export interface StructType {
@@ -93,6 +118,7 @@ export interface EnumVariant {
   name: string;
   value?: string | number;
   documentation?: string;
+  source?: SourceLocation;
 }
 
 export interface EnumType {
@@ -116,6 +142,7 @@ export interface UnionVariant {
   type: ResolvedType | null;
   documentation?: string;
   discriminatorValue?: string; // Value of the discriminator for this variant
+  source?: SourceLocation;
 }
 
 export interface LiteralType {
@@ -125,6 +152,7 @@ export interface LiteralType {
 
 export interface JsonValueType {
   kind: "json_value";
+  fallback?: string; // Why the type couldn't be converted, when it fell back to `serde_json::Value`
 }
 
 export interface TypeParameterType {
@@ -132,13 +160,46 @@ export interface TypeParameterType {
   name: string;
 }
 
+/**
+ * A reference to a type declared in a package mapped to an existing Rust crate (see `packageCrates`),
+ * generated by another conversion (see `sharedTypes`), or replacing a type excluded with `@ts2rs-exclude <RustType>`
+ * (with an empty `rustModule`)
+ */
+export interface ExternalType {
+  kind: "external";
+  name: string; // Namespace-qualified TypeScript name
+  package?: string; // Unset for shared types
+  rustModule: string;
+  typeArguments?: ResolvedType[];
+}
+
+/**
+ * An exported `const` whose initializer is literal data (`export const LIMITS = { maxItems: 50 } as const`)
+ */
+export interface ConstantType {
+  kind: "constant";
+  name: string;
+  value: ConstantValue;
+  documentation?: string;
+}
+
+export type ConstantValue =
+  | LiteralType
+  | { kind: "null" }
+  | { kind: "array"; elements: ConstantValue[] }
+  | { kind: "object"; entries: [string, ConstantValue][] };
+
 /**
  * Represents a collected type definition that needs to be generated
  */
 export interface CollectedType {
   name: string;
-  type: StructType | EnumType | UnionType | TypeAliasType;
+  type: StructType | EnumType | UnionType | TypeAliasType | ConstantType;
   sourceFile: string;
+  line?: number; // 1-based line of its declaration
+  originalName?: string; // Name before it was disambiguated from same-named types in other files
+  key?: string; // Declaring file and qualified name it was resolved from (`/src/api.ts#Api.User`)
+  allowedLints?: LintName[]; // Lints allowed by `@ts2rs-allow` on its declaration
 }
 
 /**
@@ -173,7 +234,15 @@ export interface ConversionOptions {
   entryFile: string;
 
   /**
-   * Specific type names to export. If not provided, exports all public types from entry file.
+   * The tsconfig used to resolve modules (`paths`, project references, ...). Defaults to the nearest
+   * `tsconfig.json` of the entry file.
+   */
+  tsConfigPath?: string;
+
+  /**
+   * Specific type names to export, and patterns selecting among the exported types: globs (`Api*`), alternatives
+   * (`*Request|*Response`) and exclusions (`!*Internal`). Without any name or including pattern, all exported types
+   * are selected. Types they reference are converted too.
    */
   typeNames?: string[];
 
@@ -182,6 +251,18 @@ export interface ConversionOptions {
    */
   outputPath?: string;
 
+  /**
+   * Output directory for generated Rust code: one module per TypeScript source file, declared by a `mod.rs`
+   * that re-exports their types. Takes precedence over `outputPath`.
+   */
+  outputDir?: string;
+
+  /**
+   * Rust path of the generated module (`outputDir`, or the `outputPath` file), used to import types from other
+   * modules (e.g. `crate::generated`). Defaults to the path derived from the output's location under `src`.
+   */
+  outputModulePath?: string;
+
   /**
    * Custom type mappings from TypeScript type names to Rust type names or full mapping config
    */
@@ -204,9 +285,97 @@ export interface ConversionOptions {
   customTypeAnnotations?: string[];
 
   /**
-   * Strict mode: fail on unresolvable types instead of falling back to serde_json::Value
+   * Strict mode: fail on unresolvable types instead of falling back to serde_json::Value, and on same-named types
+   * instead of disambiguating them. Same as denying the `value-fallback` and `type-collision` lints.
    */
   strict?: boolean;
+
+  /**
+   * Level of each lint, overriding `strict`: `"allow"` ignores it, `"warn"` reports it in
+   * `ConversionResult.warnings` and `"deny"` fails the conversion. `@ts2rs-allow <lint>, ...` on a declaration
+   * allows lints for it. See `DEFAULT_LINT_LEVELS` for the defaults.
+   */
+  lints?: Partial<Record<LintName, LintLevel>>;
+
+  /**
+   * Generate template literal type aliases as newtypes that validate their pattern on deserialization
+   * (`#[serde(try_from = "String")]`) instead of plain `String` aliases
+   */
+  templateLiteralNewtypes?: boolean;
+
+  /**
+   * Which class members become struct fields, in addition to public ones: `"protected"` also
+   * includes protected members and `"all"` includes private members too. Defaults to `"public"`.
+   */
+  classMemberVisibility?: "public" | "protected" | "all";
+
+  /**
+   * How types declared inside namespaces are emitted: `"modules"` (default) nests them in `pub mod` blocks
+   * mirroring the namespaces, `"flat"` declares them at the top level prefixed with their namespaces (`ApiUser`).
+   */
+  namespaces?: "modules" | "flat";
+
+  /**
+   * Names for specific types, keyed by `<file>#<TypeName>` (the file is matched against the end of the declaring
+   * file's path) or by `<TypeName>` alone, e.g. `{ "billing/types.ts#Status": "BillingStatus" }`.
+   * Same-named types from different files are disambiguated automatically unless renamed here
+   * (in strict mode such a collision is an error instead).
+   */
+  typeRenames?: Record<string, string>;
+
+  /**
+   * npm packages whose types are provided by existing Rust crates, mapped to the Rust module declaring them
+   * (e.g. `{ "@acme/shared-types": "acme_shared::types" }`). Their types are referenced from that module
+   * (`acme_shared::types::UserRole`) instead of being generated.
+   */
+  packageCrates?: Record<string, string>;
+
+  /**
+   * Types generated by other conversions, by type key (see `ConversionResult.rustPaths`), mapped to their Rust path.
+   * They are referenced from there instead of being generated again.
+   */
+  sharedTypes?: Record<string, string>;
+
+  /**
+   * Don't write the output, see `ConversionResult.selection` for the types that would be converted
+   */
+  dryRun?: boolean;
+
+  /**
+   * Don't write the output, compare it with the existing output files instead, see `ConversionResult.drift`.
+   * The header lines naming the generator and the source file are ignored.
+   */
+  checkOnly?: boolean;
+
+  /**
+   * Collect timings and project size in `ConversionResult.stats`
+   */
+  verbose?: boolean;
+
+  /**
+   * Directory to cache resolved types in between conversions. Types whose files (and the files these import)
+   * are unchanged are reused instead of resolved again, and nothing is loaded if no file changed.
+   */
+  cacheDir?: string;
+
+  /**
+   * Also write a JSON manifest of what each generated Rust item was converted from (see `Manifest`): next to
+   * `outputPath` (`types.rs` => `types.manifest.json`), or as `manifest.json` in `outputDir`
+   */
+  manifest?: boolean;
+
+  /**
+   * Comment each generated item, field and variant with where it is declared (`// from src/api.ts:42 (User.name)`),
+   * and each `serde_json::Value` a type fell back to with the reason
+   */
+  sourceComments?: boolean;
+
+  /**
+   * Keep the hand-written code of the output files between `// ts2rs:keep-start` and `// ts2rs:keep-end`: an empty
+   * region is generated after each type (named after it) and at the end of each file, and the code of the regions
+   * of the existing files is carried over to the regions of the same name
+   */
+  keepRegions?: boolean;
 }
 
 /**
@@ -226,22 +395,244 @@ export interface ConversionResult {
   /**
    * Any warnings generated during conversion
    */
-  warnings: string[];
+  warnings: Diagnostic[];
+
+  /**
+   * The generated files by path relative to `outputDir` (`rustCode` is the root `mod.rs`), when generating a directory
+   */
+  files?: Record<string, string>;
+
+  /**
+   * The types from `packageCrates` packages (or `sharedTypes`) the generated code depends on
+   */
+  externalTypes: ExternalTypeUsage[];
+
+  /**
+   * Rust path of each generated type (`crate::generated::User`, see `outputModulePath`) by its type key:
+   * the declaring file and the qualified name (`/src/api.ts#Api.User`), when known
+   */
+  rustPaths: Record<string, string>;
+
+  /**
+   * Timings and project size, when `verbose` is set
+   */
+  stats?: ConversionStats;
+
+  /**
+   * Why each type was converted (or excluded), set by `convert`
+   */
+  selection?: TypeSelection[];
+
+  /**
+   * The output files that are missing or differ from the generated code, when `checkOnly` is set
+   */
+  drift?: FileDrift[];
+
+  /**
+   * The files the types were resolved from (including the declaration files of imported packages) and the tsconfig,
+   * set by `convert`
+   */
+  inputFiles?: string[];
+
+  /**
+   * The output files written by `convert`, those whose content changed
+   */
+  writtenFiles?: string[];
+
+  /**
+   * What each generated item was converted from, when `manifest` is set
+   */
+  manifest?: Manifest;
+}
+
+/**
+ * Mapping of the generated Rust items to the TypeScript declarations they were converted from
+ */
+export interface Manifest {
+  version: 1;
+  items: ManifestItem[];
+}
+
+export interface ManifestItem {
+  tsName: string; // Qualified name (`Api.User`), before disambiguation
+  sourceFile: string; // Relative to the project, like the `// Source:` header
+  line?: number;
+  rustPath: string; // `crate::generated::User`, see `outputModulePath`
+  kind: CollectedType["type"]["kind"];
+  fields?: ManifestField[];
+  variants?: ManifestVariant[];
+  rustType?: string; // Aliased type of type aliases, type of constants
+  fallbacks?: ManifestFallback[]; // Types that fell back to `serde_json::Value`
+}
+
+export interface ManifestField {
+  tsName: string; // Key in the JSON
+  rustName: string;
+  rustType: string;
+  optional: boolean;
+}
+
+export interface ManifestVariant {
+  rustName: string;
+  value?: string | number; // Value in the JSON (string enums, numeric enums, tagged union discriminators)
+  rustType?: string; // Type of tuple variants
+  fields?: ManifestField[]; // Fields of struct variants
+}
+
+export interface ManifestFallback {
+  path?: string; // Field or variant (`settings.theme`), none if the item itself fell back
+  reason: string;
+}
+
+/**
+ * Result of comparing hand-written Rust types with the TypeScript types, see `verify`
+ */
+export interface VerifyResult {
+  /**
+   * How the JSON of the Rust types differs from the TypeScript types, as errors located in the Rust file
+   */
+  mismatches: Diagnostic[];
+
+  /**
+   * The types that have a Rust counterpart and were compared
+   */
+  verifiedTypes: string[];
+
+  /**
+   * Warnings of the resolution of the TypeScript types
+   */
+  warnings: Diagnostic[];
+}
+
+/**
+ * A warning or error of a conversion, identified by a stable code (see `DIAGNOSTIC_CODES`)
+ */
+export interface Diagnostic {
+  code: string; // `TS2RS001`
+  name: DiagnosticName;
+  severity: "warning" | "error";
+  message: string;
+  file?: string;
+  line?: number; // 1-based
+  column?: number; // 1-based
+  endLine?: number;
+  endColumn?: number;
+  typeName?: string; // Type being converted
+  fieldPath?: string; // Field of the type the diagnostic is about (`address.street`)
+  suggestion?: string;
+}
+
+export type DiagnosticName =
+  | "value-fallback" // A type is converted to serde_json::Value
+  | "anonymous-struct" // An object type without a name is converted to serde_json::Value
+  | "type-collision" // Same-named types from different files are renamed
+  | "deferred-conditional" // A generic conditional type is only converted where used
+  | "unresolvable-union" // A union with variants that can't be converted
+  | "no-types" // Nothing to convert
+  | "untagged-union" // A union converted to an untagged enum, whose variants serde tries in order
+  | "dropped-methods" // Methods of an interface or class that aren't converted
+  | "lossy-number" // A number literal type converted to f64, which accepts any number
+  | "unsupported-type" // A type that can't be serialized (e.g. Promise)
+  | "type-not-found"
+  | "conflicting-declaration" // A property declared with different types by merged interfaces
+  | "invalid-constant" // A listed constant that isn't literal data
+  | "orphaned-region" // A kept region of hand-written code whose type isn't generated anymore
+  | "missing-item" // A selected type isn't declared in the verified Rust file
+  | "missing-field" // A property isn't a field of the Rust struct
+  | "extra-field" // A field of the Rust struct isn't a property of the TypeScript type
+  | "optionality-mismatch" // A property is optional on one side only
+  | "rename-mismatch" // A field is serialized under another key than the property
+  | "type-mismatch" // A field or an item serializes to another JSON type
+  | "variant-mismatch"; // The values of an enum, or the tags of a union, differ
+
+/**
+ * Diagnostics whose level can be configured (see `ConversionOptions.lints`)
+ */
+export type LintName =
+  | "value-fallback"
+  | "anonymous-struct"
+  | "untagged-union"
+  | "dropped-methods"
+  | "lossy-number"
+  | "type-collision";
+
+export type LintLevel = "allow" | "warn" | "deny";
+
+export interface FileDrift {
+  path: string;
+  diff: string; // Unified diff from the existing file to the generated code
+}
+
+export interface TypeSelection {
+  name: string;
+  sourceFile: string;
+  reason: "listed" | "exported" | "pattern" | "dependency" | "excluded";
+  detail?: string; // The pattern matched, the type referencing it, or the Rust type replacing an excluded type
+}
+
+export interface ConversionStats {
+  sourceFiles: number; // Files loaded from the entry file's imports
+  types: number; // Types collected
+  reusedTypes: number; // Types reused from the cache
+  loadMs: number;
+  resolveMs: number;
+  generateMs: number;
+  writeMs: number;
+}
+
+export interface ExternalTypeUsage {
+  rustPath: string;
+  package?: string; // Unset for shared types
+  usedBy: string[]; // Generated types referencing it
 }
 
 /**
  * Error thrown when a TypeScript type cannot be converted to Rust
  */
 export class TypeConversionError extends Error {
+  /**
+   * All the errors of the conversion, this one first
+   */
+  public errors: Diagnostic[];
+
   constructor(
     public readonly typeName: string,
     public readonly reason: string,
     public readonly sourceFile?: string,
     public readonly line?: number,
+    public readonly details: Omit<DiagnosticDetails, "file" | "line"> & { name?: DiagnosticName } = {},
   ) {
-    super(
-      `Cannot convert TypeScript type '${typeName}' to Rust: ${reason}${sourceFile ? ` (at ${sourceFile}${line ? `:${line}` : ""})` : ""}`,
-    );
+    const location = sourceFile
+      ? [sourceFile, line, line ? details.column : undefined].filter((part) => part !== undefined).join(":")
+      : "";
+    super(`Cannot convert TypeScript type '${typeName}' to Rust: ${reason}${location ? ` (at ${location})` : ""}`);
     this.name = "TypeConversionError";
+    this.errors = [this.diagnostic];
+  }
+
+  /**
+   * An error for the errors collected during a conversion, the first one being the error's
+   */
+  static fromDiagnostics(errors: Diagnostic[]): TypeConversionError {
+    const { code, name, severity, message, file, line, typeName, ...details } = errors[0]!;
+    const error = new TypeConversionError(typeName ?? "unknown", message, file, line, { name, ...details });
+    if (errors.length > 1) {
+      error.message += ` (and ${errors.length - 1} more error(s))`;
+    }
+    error.errors = errors;
+    return error;
+  }
+
+  /**
+   * The error as a diagnostic, `unsupported-type` unless given another name
+   */
+  get diagnostic(): Diagnostic {
+    const { name = "unsupported-type", ...details } = this.details;
+    return createDiagnostic(name, "error", this.reason, {
+      ...(this.sourceFile ? { file: this.sourceFile } : {}),
+      ...(this.line ? { line: this.line } : {}),
+      typeName: this.typeName,
+      ...details,
+    });
   }
 }
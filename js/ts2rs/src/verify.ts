@@ -0,0 +1,888 @@
+/**
+ * Comparison of hand-written Rust types with the TypeScript types they mirror, by the JSON they (de)serialize
+ */
+
+import { createDiagnostic } from "./diagnostics";
+import type {
+  CollectedType,
+  ConversionOptions,
+  Diagnostic,
+  DiagnosticName,
+  ResolvedType,
+  StructField,
+  TypeSelection,
+} from "./types";
+
+interface Token {
+  kind: "ident" | "punct" | "string" | "literal";
+  text: string;
+  line: number;
+}
+
+/**
+ * A Rust type, by the last segment of its path (`Vec`, `String`, `User`), `()` for tuples and `[]` for arrays
+ */
+interface RustType {
+  name: string;
+  args: RustType[];
+  text: string;
+}
+
+/**
+ * The `#[serde(...)]` options of an item, field or variant (`rename = "id"` => `rename: "id"`, `default` => `""`)
+ */
+type SerdeOptions = Map<string, string>;
+
+interface RustField {
+  ident: string;
+  type: RustType;
+  serde: SerdeOptions;
+  line: number;
+}
+
+interface RustVariant {
+  ident: string;
+  fields?: RustField[]; // Struct variants
+  types?: RustType[]; // Tuple variants
+  discriminant?: string;
+  serde: SerdeOptions;
+  line: number;
+}
+
+type RustItem =
+  | { kind: "struct"; name: string; fields: RustField[]; serde: SerdeOptions; line: number }
+  | { kind: "enum"; name: string; variants: RustVariant[]; serde: SerdeOptions; derives: string[]; line: number }
+  | { kind: "alias"; name: string; type: RustType; line: number }; // `type X = T;` and newtypes (`struct X(T);`)
+
+const RUST_STRING_TYPES = new Set(["String", "str", "char"]);
+const RUST_NUMBER_TYPES = new Set([
+  "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32", "f64",
+  "NonZeroU8", "NonZeroU16", "NonZeroU32", "NonZeroU64", "NonZeroUsize", "NonZeroI32", "NonZeroI64",
+]);
+const RUST_ARRAY_TYPES = new Set(["Vec", "VecDeque", "HashSet", "BTreeSet", "IndexSet", "[]"]);
+const RUST_MAP_TYPES = new Set(["HashMap", "BTreeMap", "IndexMap"]);
+const RUST_WRAPPER_TYPES = new Set(["Box", "Rc", "Arc", "Cow"]); // Serialized as the type they wrap
+
+/**
+ * Compare the structs and enums of a Rust file with the collected TypeScript types of the same name, returning
+ * the differences of their JSON shape. Types selected for themselves (not as dependencies) must be declared
+ * in the Rust file.
+ */
+export function verifyRustTypes(
+  rustFile: string,
+  rustCode: string,
+  types: CollectedType[],
+  selection: TypeSelection[],
+  options: ConversionOptions,
+): { mismatches: Diagnostic[]; verifiedTypes: string[] } {
+  return new RustVerifier(rustFile, rustCode, types, options).verify(selection);
+}
+
+class RustVerifier {
+  private items: Map<string, RustItem> = new Map();
+  private types: Map<string, CollectedType> = new Map(); // By TypeScript name
+  private mismatches: Diagnostic[] = [];
+  private current: CollectedType | undefined;
+  private lines: string[];
+
+  constructor(
+    private rustFile: string,
+    rustCode: string,
+    types: CollectedType[],
+    private options: ConversionOptions,
+  ) {
+    this.lines = rustCode.split(/\r?\n/);
+    for (const item of parseRustItems(tokenize(rustCode))) {
+      this.items.set(item.name, item);
+    }
+    for (const t of types) {
+      this.types.set(t.name, t);
+    }
+  }
+
+  verify(selection: TypeSelection[]): { mismatches: Diagnostic[]; verifiedTypes: string[] } {
+    const verifiedTypes: string[] = [];
+    for (const collected of this.types.values()) {
+      if (collected.type.kind !== "struct" && collected.type.kind !== "enum" && collected.type.kind !== "union") {
+        continue;
+      }
+      this.current = collected;
+      const item = this.getItem(this.toRustName(collected.name));
+      if (!item) {
+        const selected = selection.find((s) => s.name === collected.name && s.sourceFile === collected.sourceFile);
+        if (selected && selected.reason !== "dependency") {
+          this.report("missing-item", `No struct or enum named '${this.toRustName(collected.name)}' is declared`);
+        }
+        continue;
+      }
+      verifiedTypes.push(collected.name);
+      if (collected.type.kind === "struct") {
+        this.verifyStruct(collected.type.fields, item);
+      } else if (collected.type.kind === "enum") {
+        this.verifyEnum(collected.type.variants.map((v) => String(v.value ?? v.name)), item);
+      } else {
+        this.verifyUnion(collected.type, item);
+      }
+    }
+    this.current = undefined;
+    return { mismatches: this.mismatches, verifiedTypes };
+  }
+
+  /**
+   * The struct or enum of a name, looking through aliases (`type Order = v2::Order;`)
+   */
+  private getItem(name: string): RustItem | undefined {
+    const item = this.items.get(name);
+    if (item?.kind === "alias") {
+      const target = this.items.get(this.unwrapRustType(item.type).name);
+      return target && target.kind !== "alias" ? target : item;
+    }
+    return item;
+  }
+
+  private verifyStruct(fields: StructField[], item: RustItem): void {
+    if (item.kind !== "struct") {
+      this.report("type-mismatch", `'${item.name}' is an object in TypeScript but ${describeItem(item)} in Rust`, item.line);
+      return;
+    }
+    this.verifyFields(fields, item.fields, item.serde.get("rename_all"), [], item.line);
+  }
+
+  /**
+   * Compare the fields of a struct (or of a struct variant) by their JSON keys
+   */
+  private verifyFields(
+    fields: StructField[],
+    rustFields: RustField[],
+    renameAll: string | undefined,
+    fieldPath: string[],
+    line: number,
+  ): void {
+    const keyed = new Map<string, RustField>();
+    for (const field of rustFields) {
+      if (!field.serde.has("skip") && !field.serde.has("flatten")) {
+        keyed.set(field.serde.get("rename") ?? renameField(unraw(field.ident), renameAll), field);
+      }
+    }
+    const flattened = rustFields.some((field) => field.serde.has("flatten"));
+    const tsKeys = new Set(fields.map((field) => field.name));
+
+    const matched = new Set<string>();
+    for (const field of fields) {
+      const path = [...fieldPath, field.name];
+      const rustField = keyed.get(field.name);
+      if (rustField) {
+        matched.add(field.name);
+        this.verifyField(field, rustField, path);
+        continue;
+      }
+
+      // A field of the same name serialized under another key is misnamed rather than missing
+      const misnamed = [...keyed].find(
+        ([key, f]) => !tsKeys.has(key) && !matched.has(key) && normalizeName(unraw(f.ident)) === normalizeName(field.name),
+      );
+      if (misnamed) {
+        const [key, f] = misnamed;
+        matched.add(key);
+        this.report("rename-mismatch", `Field '${field.name}' is serialized as '${key}' by '${f.ident}'`, f.line, path, {
+          suggestion: `add #[serde(rename = "${field.name}")] to the field`,
+        });
+      } else if (!flattened) {
+        this.report("missing-field", `Field '${field.name}' is missing`, line, path, {
+          suggestion: "add the field, or remove the property from the TypeScript type",
+        });
+      }
+    }
+
+    for (const [key, rustField] of keyed) {
+      if (!matched.has(key)) {
+        this.report("extra-field", `Field '${key}' isn't declared in TypeScript`, rustField.line, [...fieldPath, key], {
+          suggestion: "remove the field, or add #[serde(skip)] if it isn't serialized",
+        });
+      }
+    }
+  }
+
+  private verifyField(field: StructField, rustField: RustField, path: string[]): void {
+    const rustOptional = rustField.type.name === "Option";
+    const hasDefault = rustField.serde.has("default");
+    if (field.optional && !rustOptional && !hasDefault) {
+      this.report("optionality-mismatch", `Field '${field.name}' is optional in TypeScript but required in Rust`, rustField.line, path, {
+        suggestion: "make the field an Option, or add #[serde(default)]",
+      });
+    } else if (!field.optional && field.type.kind !== "option" && rustOptional) {
+      this.report("optionality-mismatch", `Field '${field.name}' is required in TypeScript but optional in Rust`, rustField.line, path, {
+        suggestion: "remove the Option, or make the TypeScript property optional",
+      });
+    }
+
+    // Optionality was compared above, `T | null` and `Option<T>` are compared by `T`
+    const tsType = field.optional && field.type.kind === "option" ? field.type.innerType : field.type;
+    const rustType = rustOptional && tsType.kind !== "option" ? rustField.type.args[0] : rustField.type;
+    if (rustType) {
+      const mismatch = this.compareTypes(tsType, rustType);
+      if (mismatch) {
+        this.report("type-mismatch", `Field '${field.name}' is ${mismatch}`, rustField.line, path);
+      }
+    }
+  }
+
+  private verifyEnum(values: string[], item: RustItem): void {
+    if (item.kind !== "enum") {
+      this.report("type-mismatch", `'${item.name}' is an enum in TypeScript but ${describeItem(item)} in Rust`, item.line);
+      return;
+    }
+    const repr = item.derives.includes("Deserialize_repr") || item.derives.includes("Serialize_repr");
+    let next = 0;
+    const rustValues = new Map<string, RustVariant>();
+    for (const variant of item.variants) {
+      if (repr) {
+        // serde_repr serializes the discriminants, which count up from the previous one
+        next = variant.discriminant !== undefined ? Number(variant.discriminant) : next;
+        rustValues.set(String(next++), variant);
+      } else if (!variant.serde.has("skip") && !variant.serde.has("other")) {
+        rustValues.set(this.getVariantValue(variant, item), variant);
+      }
+    }
+    this.verifyValues(values, rustValues, item);
+  }
+
+  private verifyUnion(type: Extract<ResolvedType, { kind: "union" }>, item: RustItem): void {
+    if (item.kind !== "enum") {
+      this.report("type-mismatch", `'${item.name}' is a union in TypeScript but ${describeItem(item)} in Rust`, item.line);
+      return;
+    }
+
+    const tag = item.serde.get("tag");
+    const tagged = type.discriminator !== undefined && type.variants.every((v) => typeof v.discriminatorValue === "string");
+    if (!tagged) {
+      if (tag !== undefined || !item.serde.has("untagged")) {
+        this.report("type-mismatch", `'${item.name}' is an untagged union in TypeScript but not in Rust`, item.line, [], {
+          suggestion: "add #[serde(untagged)] to the enum",
+        });
+      }
+      return;
+    }
+    if (tag !== type.discriminator) {
+      const rustTagging = tag !== undefined ? `by '${tag}'` : item.serde.has("untagged") ? "untagged" : "externally tagged";
+      this.report("type-mismatch", `'${item.name}' is tagged by '${type.discriminator}' in TypeScript but ${rustTagging} in Rust`, item.line, [], {
+        suggestion: `add #[serde(tag = "${type.discriminator}")] to the enum`,
+      });
+      return;
+    }
+
+    const rustValues = new Map<string, RustVariant>();
+    for (const variant of item.variants) {
+      if (!variant.serde.has("skip") && !variant.serde.has("other")) {
+        rustValues.set(this.getVariantValue(variant, item), variant);
+      }
+    }
+    this.verifyValues(type.variants.map((v) => v.discriminatorValue!), rustValues, item);
+
+    for (const variant of type.variants) {
+      const rustVariant = rustValues.get(variant.discriminatorValue!);
+      if (!rustVariant || variant.type === null) {
+        continue;
+      }
+      if (variant.type.kind === "struct" && variant.type.fields.length > 0) {
+        // Internally tagged variants are either struct variants or wrap a struct (`Circle(Circle)`)
+        const wrapped = rustVariant.types?.length === 1 ? this.items.get(this.unwrapRustType(rustVariant.types[0]!).name) : undefined;
+        if (wrapped?.kind === "struct") {
+          this.verifyFields(variant.type.fields, wrapped.fields, wrapped.serde.get("rename_all"), [variant.name], wrapped.line);
+        } else {
+          const renameAll = rustVariant.serde.get("rename_all") ?? item.serde.get("rename_all_fields");
+          this.verifyFields(variant.type.fields, rustVariant.fields ?? [], renameAll, [variant.name], rustVariant.line);
+        }
+      } else if (rustVariant.types?.length === 1) {
+        const mismatch = this.compareTypes(variant.type, rustVariant.types[0]!);
+        if (mismatch) {
+          this.report("type-mismatch", `Variant '${variant.discriminatorValue}' is ${mismatch}`, rustVariant.line, [variant.name]);
+        }
+      }
+    }
+  }
+
+  /**
+   * Compare the JSON values of the variants of an enum
+   */
+  private verifyValues(values: string[], rustValues: Map<string, RustVariant>, item: RustItem): void {
+    for (const value of values) {
+      if (!rustValues.has(value)) {
+        this.report("variant-mismatch", `Value '${value}' has no variant`, item.line, [], {
+          suggestion: `add a variant serialized as "${value}"`,
+        });
+      }
+    }
+    for (const [value, variant] of rustValues) {
+      if (!values.includes(value)) {
+        this.report("variant-mismatch", `Variant '${variant.ident}' is serialized as '${value}', which isn't a value in TypeScript`, variant.line);
+      }
+    }
+  }
+
+  private getVariantValue(variant: RustVariant, item: RustItem): string {
+    return variant.serde.get("rename") ?? renameVariant(variant.ident, item.kind === "enum" ? item.serde.get("rename_all") : undefined);
+  }
+
+  /**
+   * How a TypeScript type and a Rust type serialize differently (`a string in TypeScript but u32 in Rust`),
+   * or undefined if they are the same
+   */
+  private compareTypes(tsType: ResolvedType, rustType: RustType): string | undefined {
+    const rust = this.unwrapRustType(rustType);
+    const ts = this.unwrapTsType(tsType);
+    const mismatch = (): string => `${describeTsType(ts)} in TypeScript but ${rustType.text} in Rust`;
+
+    // `serde_json::Value` and type parameters (`T`) accept anything
+    if (ts.kind === "json_value" || ts.kind === "type_parameter" || rust.name === "Value" || /^[A-Z]$/.test(rust.name)) {
+      return undefined;
+    }
+    switch (ts.kind) {
+      case "primitive":
+      case "literal": {
+        const kind = ts.kind === "literal" ? typeof ts.value : ts.type;
+        const matches = kind === "string" ? RUST_STRING_TYPES.has(rust.name)
+          : kind === "number" ? RUST_NUMBER_TYPES.has(rust.name)
+          : kind === "boolean" ? rust.name === "bool"
+          : rust.name === "()";
+        return matches ? undefined : mismatch();
+      }
+      case "option":
+        if (rust.name !== "Option") {
+          return mismatch();
+        }
+        return rust.args[0] ? this.compareTypes(ts.innerType, rust.args[0]) : undefined;
+      case "array":
+      case "set":
+        if (!RUST_ARRAY_TYPES.has(rust.name)) {
+          return mismatch();
+        }
+        return rust.args[0] ? this.compareTypes(ts.elementType, rust.args[0]) : undefined;
+      case "tuple":
+        if (rust.name !== "()" || rust.args.length !== ts.elements.length) {
+          return mismatch();
+        }
+        return ts.elements.map((element, i) => this.compareTypes(element, rust.args[i]!)).find((m) => m !== undefined);
+      case "record":
+      case "map":
+        if (!RUST_MAP_TYPES.has(rust.name)) {
+          return mismatch();
+        }
+        return rust.args[1] ? this.compareTypes(ts.valueType, rust.args[1]) : undefined;
+      case "struct":
+        if (!ts.name) {
+          return undefined; // Anonymous objects are only checked to be objects where named
+        }
+        return this.compareNames(ts.name, rust) ? undefined : mismatch();
+      case "enum":
+      case "union":
+      case "type_alias":
+        return this.compareNames(ts.name, rust) ? undefined : mismatch();
+      case "external":
+        return ts.name.split(".").pop() === rust.name ? undefined : mismatch();
+      default:
+        return undefined;
+    }
+  }
+
+  /**
+   * Whether a named TypeScript type is the Rust type: the type of the same name, or its custom mapping
+   */
+  private compareNames(tsName: string, rust: RustType): boolean {
+    const mapping = this.options.customTypeMappings?.[tsName];
+    if (mapping) {
+      const rustType = typeof mapping === "string" ? mapping : mapping.rustType;
+      return rustType.replace(/<.*$/, "").split("::").pop() === rust.name;
+    }
+    return this.toRustName(tsName) === (rust.name === "Self" ? this.toRustName(this.current!.name) : rust.name);
+  }
+
+  /**
+   * Look through boxes, and through the aliases and newtypes declared in the Rust file
+   */
+  private unwrapRustType(type: RustType, seen: Set<string> = new Set()): RustType {
+    if (RUST_WRAPPER_TYPES.has(type.name) && type.args.length > 0) {
+      return this.unwrapRustType(type.args[type.args.length - 1]!, seen);
+    }
+    const item = this.items.get(type.name);
+    if (item?.kind === "alias" && !seen.has(type.name)) {
+      return this.unwrapRustType(item.type, seen.add(type.name));
+    }
+    return type;
+  }
+
+  /**
+   * Look through boxes, and through the type aliases collected under their own name
+   */
+  private unwrapTsType(type: ResolvedType, seen: Set<string> = new Set()): ResolvedType {
+    if (type.kind === "box") {
+      return this.unwrapTsType(type.innerType, seen);
+    }
+    if ((type.kind === "struct" || type.kind === "type_alias") && type.name && !seen.has(type.name)) {
+      const collected = this.types.get(type.name);
+      if (collected?.type.kind === "type_alias" && !this.items.has(this.toRustName(type.name))) {
+        return this.unwrapTsType(collected.type.aliasedType, seen.add(type.name));
+      }
+    }
+    return type;
+  }
+
+  private toRustName(tsName: string): string {
+    return this.options.namespaces === "flat" ? tsName.split(".").join("") : tsName.split(".").pop()!;
+  }
+
+  private report(
+    name: DiagnosticName,
+    message: string,
+    line?: number,
+    fieldPath: string[] = [],
+    details: { suggestion?: string } = {},
+  ): void {
+    const collected = this.current;
+    this.mismatches.push(createDiagnostic(name, "error", message, {
+      file: this.rustFile,
+      // At the start of the line's code, so the indentation isn't underlined
+      ...(line !== undefined ? { line, column: Math.max(0, this.lines[line - 1]?.search(/\S/) ?? 0) + 1 } : {}),
+      ...(collected ? { typeName: collected.name } : {}),
+      ...(fieldPath.length > 0 ? { fieldPath: fieldPath.join(".") } : {}),
+      ...details,
+    }));
+  }
+}
+
+function describeItem(item: RustItem): string {
+  if (item.kind === "alias") {
+    return `an alias of ${item.type.text}`;
+  }
+  return item.kind === "struct" ? "a struct" : "an enum";
+}
+
+function describeTsType(type: ResolvedType): string {
+  switch (type.kind) {
+    case "primitive":
+      return `${type.type === "null" || type.type === "undefined" ? "" : "a "}${type.type}`;
+    case "literal":
+      return `the literal ${JSON.stringify(type.value)}`;
+    case "option":
+      return `a nullable ${describeTsType(type.innerType).replace(/^an? /, "")}`;
+    case "array":
+    case "set":
+      return "an array";
+    case "tuple":
+      return `a tuple of ${type.elements.length}`;
+    case "record":
+    case "map":
+      return "a map";
+    case "struct":
+    case "enum":
+    case "union":
+    case "type_alias":
+    case "external":
+      return type.name ? `'${type.name}'` : "an object";
+    default:
+      return "any value";
+  }
+}
+
+/**
+ * The key serde serializes a field under with `#[serde(rename_all = "...")]`
+ */
+function renameField(ident: string, renameAll: string | undefined): string {
+  const words = ident.split("_").filter((word) => word);
+  switch (renameAll) {
+    case "lowercase":
+    case "snake_case":
+      return ident;
+    case "UPPERCASE":
+    case "SCREAMING_SNAKE_CASE":
+      return ident.toUpperCase();
+    case "PascalCase":
+      return words.map(capitalize).join("");
+    case "camelCase":
+      return words.map((word, i) => (i === 0 ? word : capitalize(word))).join("");
+    case "kebab-case":
+      return ident.replace(/_/g, "-");
+    case "SCREAMING-KEBAB-CASE":
+      return ident.replace(/_/g, "-").toUpperCase();
+    default:
+      return ident;
+  }
+}
+
+/**
+ * The value serde serializes a unit variant as with `#[serde(rename_all = "...")]`
+ */
+function renameVariant(ident: string, renameAll: string | undefined): string {
+  const snake = ident.replace(/([a-z0-9])([A-Z])/g, "$1_$2").toLowerCase();
+  switch (renameAll) {
+    case "lowercase":
+      return ident.toLowerCase();
+    case "UPPERCASE":
+      return ident.toUpperCase();
+    case "camelCase":
+      return ident.charAt(0).toLowerCase() + ident.slice(1);
+    case "snake_case":
+      return snake;
+    case "SCREAMING_SNAKE_CASE":
+      return snake.toUpperCase();
+    case "kebab-case":
+      return snake.replace(/_/g, "-");
+    case "SCREAMING-KEBAB-CASE":
+      return snake.replace(/_/g, "-").toUpperCase();
+    default:
+      return ident;
+  }
+}
+
+function capitalize(word: string): string {
+  return word.charAt(0).toUpperCase() + word.slice(1);
+}
+
+function unraw(ident: string): string {
+  return ident.replace(/^r#/, "");
+}
+
+/**
+ * A name without its case and separators, to match fields named in different conventions (`userId`, `user_id`)
+ */
+function normalizeName(name: string): string {
+  return name.replace(/[^A-Za-z0-9]/g, "").toLowerCase();
+}
+
+/**
+ * Split Rust code into tokens, without comments
+ */
+function tokenize(code: string): Token[] {
+  const tokens: Token[] = [];
+  let line = 1;
+  let i = 0;
+  while (i < code.length) {
+    const char = code[i]!;
+    const rest = code.slice(i);
+    let match: RegExpMatchArray | null;
+    if (char === "\n") {
+      line++;
+      i++;
+    } else if (/\s/.test(char)) {
+      i++;
+    } else if (rest.startsWith("//")) {
+      const end = code.indexOf("\n", i);
+      i = end === -1 ? code.length : end;
+    } else if (rest.startsWith("/*")) {
+      // Block comments nest in Rust
+      let depth = 0;
+      do {
+        if (code.startsWith("/*", i)) {
+          depth++;
+          i += 2;
+        } else if (code.startsWith("*/", i)) {
+          depth--;
+          i += 2;
+        } else {
+          line += code[i] === "\n" ? 1 : 0;
+          i++;
+        }
+      } while (depth > 0 && i < code.length);
+    } else if ((match = rest.match(/^b?r(#*)"/))) {
+      const end = code.indexOf(`"${match[1]}`, i + match[0].length);
+      const stop = end === -1 ? code.length : end + 1 + match[1]!.length;
+      const text = code.slice(i + match[0].length, end === -1 ? code.length : end);
+      tokens.push({ kind: "string", text, line });
+      line += text.split("\n").length - 1;
+      i = stop;
+    } else if ((match = rest.match(/^b?"((?:[^"\\]|\\[\s\S])*)"/))) {
+      tokens.push({ kind: "string", text: match[1]!.replace(/\\(.)/g, "$1"), line });
+      line += match[0].split("\n").length - 1;
+      i += match[0].length;
+    } else if ((match = rest.match(/^b?'(?:[^'\\]|\\.[^']*)'/))) {
+      tokens.push({ kind: "literal", text: match[0], line });
+      i += match[0].length;
+    } else if ((match = rest.match(/^'[A-Za-z_]\w*/))) {
+      tokens.push({ kind: "literal", text: match[0], line }); // Lifetime
+      i += match[0].length;
+    } else if ((match = rest.match(/^(?:r#)?[A-Za-z_]\w*/))) {
+      tokens.push({ kind: "ident", text: match[0], line });
+      i += match[0].length;
+    } else if ((match = rest.match(/^-?[0-9][\w.]*/))) {
+      tokens.push({ kind: "literal", text: match[0], line });
+      i += match[0].length;
+    } else {
+      const text = rest.startsWith("::") ? "::" : char;
+      tokens.push({ kind: "punct", text, line });
+      i += text.length;
+    }
+  }
+  return tokens;
+}
+
+/**
+ * The structs, enums, type aliases and newtypes declared in Rust code, at any depth of modules
+ */
+function parseRustItems(tokens: Token[]): RustItem[] {
+  const items: RustItem[] = [];
+  let attributes: Token[][] = [];
+  let i = 0;
+  while (i < tokens.length) {
+    const token = tokens[i]!;
+    if (token.text === "#" && tokens[i + 1]?.text === "[") {
+      const end = findClosing(tokens, i + 1);
+      attributes.push(tokens.slice(i + 2, end));
+      i = end + 1;
+      continue;
+    }
+    if (token.kind === "ident" && (token.text === "impl" || token.text === "trait" || token.text === "fn")) {
+      // Associated types and items local to functions aren't types of the file
+      const body = findAtDepth(tokens, i + 1, ["{", ";"]);
+      i = tokens[body]?.text === "{" ? findClosing(tokens, body) + 1 : body + 1;
+      attributes = [];
+      continue;
+    }
+    if (token.kind === "ident" && (token.text === "struct" || token.text === "enum" || token.text === "type")) {
+      const name = tokens[i + 1];
+      if (name?.kind === "ident") {
+        const parsed = parseItem(tokens, i, attributes);
+        if (parsed.item) {
+          items.push(parsed.item);
+        }
+        i = parsed.end;
+        attributes = [];
+        continue;
+      }
+    }
+    if (token.text === ";" || token.text === "{" || token.text === "}") {
+      attributes = [];
+    }
+    i++;
+  }
+  return items;
+}
+
+function parseItem(tokens: Token[], start: number, attributes: Token[][]): { item?: RustItem; end: number } {
+  const keyword = tokens[start]!;
+  const name = tokens[start + 1]!.text;
+  let i = start + 2;
+  if (tokens[i]?.text === "<") {
+    i = skipGenerics(tokens, i);
+  }
+  const serde = parseSerdeOptions(attributes);
+
+  if (keyword.text === "type") {
+    if (tokens[i]?.text !== "=") {
+      return { end: i }; // An associated type
+    }
+    const end = findAtDepth(tokens, i + 1, [";"]);
+    return { item: { kind: "alias", name, type: parseType(tokens.slice(i + 1, end)), line: keyword.line }, end: end + 1 };
+  }
+
+  // Skip `where` clauses
+  while (i < tokens.length && tokens[i]!.text !== "{" && tokens[i]!.text !== "(" && tokens[i]!.text !== ";") {
+    i++;
+  }
+  const open = tokens[i];
+  if (!open || open.text === ";") {
+    return { item: { kind: "struct", name, fields: [], serde, line: keyword.line }, end: i + 1 };
+  }
+  const close = findClosing(tokens, i);
+  const body = tokens.slice(i + 1, close);
+
+  if (keyword.text === "enum") {
+    const derives = attributes
+      .filter((attr) => attr[0]?.text === "derive")
+      .flatMap((attr) => attr.filter((t) => t.kind === "ident").map((t) => t.text));
+    return { item: { kind: "enum", name, variants: parseVariants(body), serde, derives, line: keyword.line }, end: close + 1 };
+  }
+  if (open.text === "(") {
+    const types = splitAtDepth(body).map((field) => parseType(stripFieldPrefix(field).tokens));
+    const item: RustItem = types.length === 1
+      ? { kind: "alias", name, type: types[0]!, line: keyword.line }
+      : { kind: "struct", name, fields: [], serde, line: keyword.line };
+    return { item, end: close + 1 };
+  }
+  return { item: { kind: "struct", name, fields: parseFields(body), serde, line: keyword.line }, end: close + 1 };
+}
+
+function parseFields(body: Token[]): RustField[] {
+  return splitAtDepth(body).flatMap((field) => {
+    const { attributes, tokens } = stripFieldPrefix(field);
+    const colon = tokens.findIndex((t) => t.text === ":");
+    if (colon === -1 || !tokens[0]) {
+      return [];
+    }
+    return [{ ident: tokens[0].text, type: parseType(tokens.slice(colon + 1)), serde: parseSerdeOptions(attributes), line: tokens[0].line }];
+  });
+}
+
+function parseVariants(body: Token[]): RustVariant[] {
+  return splitAtDepth(body).flatMap((variant) => {
+    const { attributes, tokens } = stripFieldPrefix(variant);
+    const ident = tokens[0];
+    if (!ident) {
+      return [];
+    }
+    const result: RustVariant = { ident: ident.text, serde: parseSerdeOptions(attributes), line: ident.line };
+    const next = tokens[1];
+    if (next?.text === "{") {
+      result.fields = parseFields(tokens.slice(2, findClosing(tokens, 1)));
+    } else if (next?.text === "(") {
+      result.types = splitAtDepth(tokens.slice(2, findClosing(tokens, 1))).map((t) => parseType(t));
+    } else if (next?.text === "=") {
+      result.discriminant = tokens.slice(2).map((t) => t.text).join("");
+    }
+    return [result];
+  });
+}
+
+/**
+ * The attributes and the tokens of a field or variant, without its visibility
+ */
+function stripFieldPrefix(tokens: Token[]): { attributes: Token[][]; tokens: Token[] } {
+  const attributes: Token[][] = [];
+  let i = 0;
+  while (tokens[i]?.text === "#" && tokens[i + 1]?.text === "[") {
+    const end = findClosing(tokens, i + 1);
+    attributes.push(tokens.slice(i + 2, end));
+    i = end + 1;
+  }
+  if (tokens[i]?.text === "pub") {
+    i = tokens[i + 1]?.text === "(" ? findClosing(tokens, i + 1) + 1 : i + 1;
+  }
+  return { attributes, tokens: tokens.slice(i) };
+}
+
+/**
+ * The options of the `serde` attributes, `rename(deserialize = "...")` counting as `rename`
+ */
+function parseSerdeOptions(attributes: Token[][]): SerdeOptions {
+  const options: SerdeOptions = new Map();
+  for (const attr of attributes) {
+    if (attr[0]?.text !== "serde" || attr[1]?.text !== "(") {
+      continue;
+    }
+    for (const option of splitAtDepth(attr.slice(2, findClosing(attr, 1)))) {
+      const key = option[0]?.text;
+      if (!key) {
+        continue;
+      }
+      if (option[1]?.text === "=") {
+        options.set(key, option[2]?.text ?? "");
+      } else if (option[1]?.text === "(") {
+        const nested = splitAtDepth(option.slice(2, findClosing(option, 1)));
+        const deserialize = nested.find((n) => n[0]?.text === "deserialize");
+        options.set(key, deserialize?.[2]?.text ?? "");
+      } else {
+        options.set(key, "");
+      }
+    }
+  }
+  return options;
+}
+
+function parseType(tokens: Token[]): RustType {
+  let i = 0;
+  // References and lifetimes (`&'a str`), `dyn` and `impl` don't change the serialization
+  while (tokens[i] && (tokens[i]!.text === "&" || tokens[i]!.text === "mut" || tokens[i]!.text === "dyn" || tokens[i]!.text.startsWith("'"))) {
+    i++;
+  }
+  const text = tokens
+    .map((t) => (t.text === "," || t.text.startsWith("'") || ["dyn", "mut", "impl"].includes(t.text) ? `${t.text} ` : t.text))
+    .join("")
+    .trim();
+  const first = tokens[i];
+  if (!first) {
+    return { name: "()", args: [], text };
+  }
+  if (first.text === "(") {
+    const close = findClosing(tokens, i);
+    return { name: "()", args: splitAtDepth(tokens.slice(i + 1, close)).map((t) => parseType(t)), text };
+  }
+  if (first.text === "[") {
+    const close = findClosing(tokens, i);
+    const elementEnd = findAtDepth(tokens, i + 1, [";", "]"]);
+    return { name: "[]", args: [parseType(tokens.slice(i + 1, Math.min(elementEnd, close)))], text };
+  }
+
+  // The last segment of the path, with its generic arguments
+  let name = first.text;
+  let args: RustType[] = [];
+  while (i < tokens.length) {
+    const token = tokens[i]!;
+    if (token.kind === "ident") {
+      name = token.text;
+      i++;
+    } else if (token.text === "::") {
+      i++;
+    } else if (token.text === "<") {
+      const close = findClosing(tokens, i);
+      args = splitAtDepth(tokens.slice(i + 1, close))
+        .filter((arg) => !arg[0]?.text.startsWith("'"))
+        .map((arg) => parseType(arg));
+      i = close + 1;
+    } else {
+      break;
+    }
+  }
+  return { name, args, text };
+}
+
+const OPENING: Record<string, string> = { "(": ")", "[": "]", "{": "}", "<": ">" };
+
+/**
+ * The index of the token closing the bracket at `start`
+ */
+function findClosing(tokens: Token[], start: number): number {
+  const stack: string[] = [];
+  for (let i = start; i < tokens.length; i++) {
+    const text = tokens[i]!.text;
+    if (text === ">" && tokens[i - 1]?.text === "-") {
+      continue; // `->` of function types
+    }
+    if (OPENING[text]) {
+      stack.push(OPENING[text]!);
+    } else if (text === stack[stack.length - 1]) {
+      stack.pop();
+      if (stack.length === 0) {
+        return i;
+      }
+    }
+  }
+  return tokens.length;
+}
+
+function skipGenerics(tokens: Token[], start: number): number {
+  return findClosing(tokens, start) + 1;
+}
+
+/**
+ * The index of the first of `stops` outside of brackets, from `start`
+ */
+function findAtDepth(tokens: Token[], start: number, stops: string[]): number {
+  let depth = 0;
+  for (let i = start; i < tokens.length; i++) {
+    const text = tokens[i]!.text;
+    if (depth === 0 && stops.includes(text)) {
+      return i;
+    }
+    if (text in OPENING) {
+      depth++;
+    } else if (text === ")" || text === "]" || text === "}" || (text === ">" && tokens[i - 1]?.text !== "-")) {
+      depth--;
+    }
+  }
+  return tokens.length;
+}
+
+/**
+ * Split tokens at the commas outside of brackets, without empty parts (trailing commas)
+ */
+function splitAtDepth(tokens: Token[]): Token[][] {
+  const parts: Token[][] = [];
+  let start = 0;
+  while (start < tokens.length) {
+    const end = findAtDepth(tokens, start, [","]);
+    if (end > start) {
+      parts.push(tokens.slice(start, end));
+    }
+    start = end + 1;
+  }
+  return parts;
+}
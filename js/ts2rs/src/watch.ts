@@ -0,0 +1,133 @@
+/**
+ * Watch mode: run the jobs of a project again when the files they were converted from change
+ */
+
+import * as fs from "node:fs";
+import * as path from "node:path";
+import { convert, type JobResult } from "./index";
+import { getJobName, getJobOptions, type JobOptions, type ProjectConfig } from "./config";
+import type { ConversionOptions } from "./types";
+
+/**
+ * Delay after the last change before converting, so a burst of saves triggers a single run
+ */
+const DEBOUNCE_MS = 100;
+
+export interface WatchRun {
+  changedFiles: string[]; // Empty for the first run
+  results: JobResult[]; // Jobs that ran, in order
+  errors: { name: string; error: unknown }[]; // Jobs that failed, watched again once their files change
+}
+
+export interface Watcher {
+  close(): void;
+}
+
+interface WatchedJob {
+  name: string;
+  options: ConversionOptions;
+  inputFiles: Set<string>; // Files of the last successful run
+  rustPaths: Record<string, string>; // Shared with the later jobs (see `sharedTypes`)
+}
+
+/**
+ * Run the jobs of a project configuration (see `convertProject`), then run again the jobs reading files that
+ * changed, and the later jobs referencing types whose Rust paths changed. Errors are reported to `onRun` instead
+ * of ending the watch.
+ */
+export async function watchProject(
+  config: ProjectConfig,
+  configDir: string,
+  overrides: Partial<JobOptions>,
+  onRun: (run: WatchRun) => void,
+): Promise<Watcher> {
+  const jobs: WatchedJob[] = config.jobs.map((job) => {
+    const options: ConversionOptions = { ...getJobOptions(config, job), ...overrides };
+    const inputFiles = new Set([path.resolve(options.entryFile)]);
+    return { name: getJobName(job, configDir), options, inputFiles, rustPaths: {} };
+  });
+  const directories = new Map<string, fs.FSWatcher>();
+  const changedFiles = new Set<string>();
+  let timer: ReturnType<typeof setTimeout> | undefined;
+  let running: Promise<void> | undefined;
+  let closed = false;
+
+  const run = async (changed: string[]): Promise<void> => {
+    const watchRun: WatchRun = { changedFiles: changed, results: [], errors: [] };
+    const sharedTypes: Record<string, string> = {};
+    let sharedTypesChanged = false;
+    for (const job of jobs) {
+      if (changed.length === 0 || sharedTypesChanged || changed.some((file) => job.inputFiles.has(file))) {
+        try {
+          const result = await convert({ ...job.options, sharedTypes: { ...sharedTypes } });
+          job.inputFiles = new Set((result.inputFiles ?? []).map((file) => path.resolve(file)));
+          sharedTypesChanged ||= JSON.stringify(result.rustPaths) !== JSON.stringify(job.rustPaths);
+          job.rustPaths = result.rustPaths;
+          watchRun.results.push({ name: job.name, options: job.options, result });
+        } catch (error) {
+          job.inputFiles.add(path.resolve(job.options.entryFile));
+          watchRun.errors.push({ name: job.name, error });
+        }
+      }
+      for (const [key, rustPath] of Object.entries(job.rustPaths)) {
+        sharedTypes[key] ??= rustPath;
+      }
+    }
+    if (!closed) {
+      updateWatchedDirectories();
+      onRun(watchRun);
+    }
+  };
+
+  // Directories are watched rather than files, since editors often save by replacing the file
+  const updateWatchedDirectories = (): void => {
+    const watched = new Set(closed ? [] : jobs.flatMap((job) => [...job.inputFiles].map((file) => path.dirname(file))));
+    for (const [directory, watcher] of directories) {
+      if (!watched.has(directory)) {
+        watcher.close();
+        directories.delete(directory);
+      }
+    }
+    for (const directory of watched) {
+      if (!directories.has(directory) && fs.existsSync(directory)) {
+        const watcher = fs.watch(directory, (_, fileName) => {
+          if (fileName) {
+            onChange(path.join(directory, fileName));
+          }
+        });
+        directories.set(directory, watcher);
+      }
+    }
+  };
+
+  const onChange = (file: string): void => {
+    if (!jobs.some((job) => job.inputFiles.has(file))) {
+      return;
+    }
+    changedFiles.add(file);
+    clearTimeout(timer);
+    timer = setTimeout(flush, DEBOUNCE_MS);
+  };
+
+  // Runs don't overlap, changes made during a run trigger the next one
+  const flush = (): void => {
+    if (running) {
+      timer = setTimeout(flush, DEBOUNCE_MS);
+      return;
+    }
+    const changed = [...changedFiles];
+    changedFiles.clear();
+    running = run(changed).finally(() => {
+      running = undefined;
+    });
+  };
+
+  await run([]);
+  return {
+    close: () => {
+      closed = true;
+      clearTimeout(timer);
+      updateWatchedDirectories();
+    },
+  };
+}
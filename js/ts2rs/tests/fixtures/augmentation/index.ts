@@ -0,0 +1,7 @@
+/**
+ * Interfaces augmented by a file of the project that nothing imports
+ */
+
+export interface Account {
+  id: string;
+}
@@ -0,0 +1,11 @@
+/**
+ * Module augmentation of `Account` from `./index`, not imported by it
+ */
+
+declare module "./index" {
+  interface Account {
+    plan?: string;
+  }
+}
+
+export {};
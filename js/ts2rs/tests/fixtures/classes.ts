@@ -0,0 +1,50 @@
+/**
+ * Classes used as struct sources (e.g. class-validator DTOs)
+ */
+
+export class BaseDto {
+  id!: string;
+  createdAt: string = "";
+}
+
+export class UserDto extends BaseDto {
+  name = "";
+  age: number = 18;
+  active = true;
+  tags: string[] = [];
+  nickname?: string;
+  private secret?: string;
+  protected internalNote = "";
+  #hidden = 1;
+  static version = 2;
+  onChange = () => {};
+
+  constructor(public role: string = "member") {
+    super();
+  }
+
+  get displayName(): string {
+    return this.nickname ?? this.name;
+  }
+
+  greet(): string {
+    return `Hello ${this.name}`;
+  }
+}
+
+export class AdminDto extends UserDto {
+  override nickname: string = "admin";
+}
+
+export interface PageSettings {
+  dense?: boolean;
+}
+
+export class PageDto<T> {
+  items: T[] = [];
+  size: number = 20;
+  cursor: string | null = null;
+  // @ts-expect-error the initializer doesn't match the type, so it isn't a default
+  label: string = null;
+  settings: PageSettings = {};
+}
@@ -0,0 +1 @@
+export type Status = "pending" | "paid" | "refunded";
@@ -0,0 +1,12 @@
+/**
+ * Test fixtures for same-named types declared in different files
+ */
+
+import { Status } from "./status";
+import { Status as PaymentStatus } from "./billing/status";
+
+export interface Order {
+  id: string;
+  delivery: Status;
+  payment: PaymentStatus;
+}
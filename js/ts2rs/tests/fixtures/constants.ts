@@ -0,0 +1,53 @@
+/**
+ * Test fixtures for `as const` enum-objects and exported constants
+ */
+
+export const Role = {
+  Admin: "admin",
+  SuperUser: "super-user",
+  READ_ONLY: "read_only",
+} as const;
+export type Role = typeof Role[keyof typeof Role];
+
+export const HttpStatus = {
+  Ok: 200,
+  NotFound: 404,
+} as const;
+export type HttpStatus = (typeof HttpStatus)[keyof typeof HttpStatus];
+
+// Keys that aren't valid variant names, or that collide once converted
+export const ErrorCode = {
+  1: "unknown",
+  "404": "not-found",
+  "not-found": "missing",
+  FOO_BAR: "foo_bar",
+  fooBar: "foo-bar",
+} as const;
+export type ErrorCode = (typeof ErrorCode)[keyof typeof ErrorCode];
+
+export interface Account {
+  id: string;
+  role: Role;
+  lastStatus?: HttpStatus;
+}
+
+/** Version of the public API */
+export const apiVersion = "v2";
+export const MAX_RETRIES = 3;
+export const TIMEOUT_SECONDS = -1.5;
+export const DEBUG = false;
+export const SUPPORTED_LOCALES = ["en", "fr", "de"] as const;
+export const BACKOFF_MS = [100, 200, 400];
+
+export const LIMITS = {
+  maxItems: 50,
+  retry: { attempts: 3, backoff: [1, 2] },
+  label: null,
+} as const;
+
+// Not literal data, so these are skipped
+export const computed = MAX_RETRIES * 2;
+export function helper(): string {
+  return apiVersion;
+}
+export let mutable = "value";
@@ -0,0 +1,30 @@
+/**
+ * Types converted with warnings, for the diagnostics tests
+ */
+export interface Job {
+  id: string;
+  priority: "low" | "high";
+}
+
+/**
+ * @ts2rs-allow value-fallback
+ */
+export interface Task {
+  mode: "manual" | "scheduled";
+}
+
+export class Worker {
+  name: string = "";
+
+  run(): void {}
+}
+
+export interface Circle {
+  radius: number;
+}
+
+export interface Square {
+  side: number;
+}
+
+export type Shape = Circle | Square;
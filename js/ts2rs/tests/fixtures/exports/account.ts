@@ -0,0 +1,6 @@
+import type { InternalUser } from "./internal";
+
+export default interface Account {
+  owner: InternalUser;
+  createdAt: string;
+}
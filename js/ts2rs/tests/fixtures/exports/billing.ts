@@ -0,0 +1,3 @@
+export interface Invoice {
+  total: number;
+}
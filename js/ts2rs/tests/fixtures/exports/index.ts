@@ -0,0 +1,12 @@
+/**
+ * Test fixtures for types exported under other names, through barrel files and as default exports
+ */
+
+export { InternalUser as User } from "./internal";
+export * from "./models";
+export * as billing from "./billing";
+export { default as Account } from "./account";
+
+export default interface Settings {
+  theme: string;
+}
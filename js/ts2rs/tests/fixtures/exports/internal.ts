@@ -0,0 +1,4 @@
+export interface InternalUser {
+  id: string;
+  name: string;
+}
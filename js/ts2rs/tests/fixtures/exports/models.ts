@@ -0,0 +1,9 @@
+import type { InternalUser } from "./internal";
+
+export type Visibility = "public" | "private";
+
+export interface Project {
+  id: string;
+  visibility: Visibility;
+  members: InternalUser[];
+}
@@ -0,0 +1,11 @@
+/**
+ * Module augmentation of `Account` from `./merged-interfaces`
+ */
+
+declare module "./merged-interfaces" {
+  interface Account {
+    plan?: string;
+  }
+}
+
+export {};
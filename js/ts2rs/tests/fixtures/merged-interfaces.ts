@@ -0,0 +1,36 @@
+/**
+ * Interfaces declared several times and augmented from another module
+ */
+
+import "./merged-interfaces-augmentation";
+
+/** An account in the billing system */
+export interface Account {
+  id: string;
+}
+
+export interface Account {
+  email: string;
+  id: string;
+}
+
+export interface Conflicting {
+  value: string;
+}
+
+export interface Conflicting {
+  value: number;
+}
+
+interface Audited {
+  createdAt: string;
+  updatedBy?: string;
+}
+
+export interface Invoice extends Audited {
+  total: number;
+}
+
+export interface Invoice extends Audited {
+  updatedBy: string;
+}
@@ -0,0 +1,13 @@
+/**
+ * Test fixtures for source files named like `mod.rs`, or named the same module once sanitized
+ */
+
+import type { Settings } from "./mod";
+import type { Profile } from "./user-profile";
+import type { Avatar } from "./user_profile";
+
+export interface Account {
+  settings: Settings;
+  profile: Profile;
+  avatar: Avatar;
+}
@@ -0,0 +1,3 @@
+export interface Settings {
+  theme: string;
+}
@@ -0,0 +1,3 @@
+export interface Profile {
+  name: string;
+}
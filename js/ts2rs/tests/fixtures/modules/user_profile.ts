@@ -0,0 +1,3 @@
+export interface Avatar {
+  url: string;
+}
@@ -0,0 +1,15 @@
+/**
+ * Test fixtures for imports through tsconfig paths, workspace packages and project references
+ */
+
+import type { Theme } from "@app/theme";
+import type { UserRole } from "@acme/shared";
+import type { Permission } from "@acme/shared/permissions";
+import type { LegacyRecord } from "@acme/legacy";
+
+export interface Member {
+  role: UserRole;
+  permissions: Permission[];
+  theme: Theme;
+  legacy: LegacyRecord;
+}
@@ -0,0 +1 @@
+export type Theme = "light" | "dark";
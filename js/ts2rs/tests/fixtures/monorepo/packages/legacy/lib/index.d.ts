@@ -0,0 +1,3 @@
+export interface LegacyRecord {
+    legacyId: number;
+}
@@ -0,0 +1 @@
+export type UserRole = "admin" | "member";
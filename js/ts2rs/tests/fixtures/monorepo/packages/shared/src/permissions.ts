@@ -0,0 +1,4 @@
+export interface Permission {
+  resource: string;
+  write: boolean;
+}
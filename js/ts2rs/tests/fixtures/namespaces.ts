@@ -0,0 +1,41 @@
+/**
+ * Test fixtures for types declared inside namespaces
+ */
+
+export interface Team {
+  name: string;
+}
+
+export namespace Api {
+  export type Role = "admin" | "member";
+
+  export interface User {
+    id: string;
+    team: Team;
+    role: Role;
+  }
+
+  export namespace V1 {
+    export interface User {
+      legacyId: number;
+      current: Api.User;
+    }
+  }
+}
+
+export namespace Admin {
+  export interface User {
+    permissions: string[];
+  }
+}
+
+export interface Session {
+  user: Api.User;
+  admin: Admin.User | null;
+  legacy?: Api.V1.User;
+}
+
+// Takes the name `Api.User` would get in flat mode
+export interface ApiUser {
+  flat: boolean;
+}
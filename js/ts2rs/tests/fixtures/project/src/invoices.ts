@@ -0,0 +1,6 @@
+import type { Money } from "./common";
+
+export interface Invoice {
+  number: string;
+  amount: Money;
+}
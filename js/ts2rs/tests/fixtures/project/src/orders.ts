@@ -0,0 +1,6 @@
+import type { Money } from "./common";
+
+export interface Order {
+  id: string;
+  total: Money;
+}
@@ -144,3 +144,36 @@ export interface CustomMappingTest {
   externalData: CustomExternalType;
   optionalExternal?: CustomExternalType;
 }
+
+// Mapped types over literal key unions
+export type Locale = "en" | "de-DE";
+export type Feature = "darkMode" | "beta";
+
+export type Translations = Record<Locale, string>;
+
+export type FeatureFlags = { readonly [K in Feature]?: boolean };
+
+export interface LocalizedSettings {
+  labels: Record<Locale, string>;
+  flags: FeatureFlags;
+  counters: Record<string, number>;
+}
+
+export interface LocalizedPage {
+  title: Record<Locale, string>;
+}
+
+// Declared with the name the inline mapped type of `LocalizedPage.title` would get
+export interface LocalizedPageTitle {
+  fallback: string;
+}
+
+// Conditional and template literal types
+export type UserId = `usr-${number}`;
+export type Id<T> = T extends User ? UserId : string;
+
+export interface Session {
+  owner: Id<User>;
+  device: Id<number>;
+  token: `tok_${string}`;
+}
@@ -0,0 +1,46 @@
+/**
+ * Test fixtures for selecting types with patterns and `@ts2rs-exclude`
+ */
+
+export interface ApiUser {
+  id: string;
+  profile: Profile;
+  audit: Audit;
+}
+
+interface Profile {
+  name: string;
+}
+
+/**
+ * Server-side bookkeeping
+ * @ts2rs-exclude
+ */
+export interface Audit {
+  createdBy: string;
+  entries: AuditEntry[];
+}
+
+export interface AuditEntry {
+  at: number;
+}
+
+export interface CreateUserRequest {
+  name: string;
+  createdAt: Timestamp;
+}
+
+export interface CreateUserResponse {
+  user: ApiUser;
+}
+
+/** @ts2rs-exclude chrono::DateTime<chrono::Utc> */
+export interface Timestamp {
+  seconds: number;
+}
+
+export interface CacheEntryInternal {
+  key: string;
+}
+
+export type Helper = { value: number };
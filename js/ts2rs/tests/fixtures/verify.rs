@@ -0,0 +1,62 @@
+//! Hand-written mirror of verify.ts, for the verify tests
+
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: u64,
+    pub note: String,
+    pub retries: Option<u32>,
+    pub priority: Priority,
+    pub status: Status,
+}
+
+#[derive(Debug, Clone, Copy, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum Priority {
+    Low = 1,
+    Normal,
+    Urgent = 5,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Status {
+    Active,
+    OnHold,
+    Finished,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Task {
+    pub mode: String,
+    pub retries: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Circle {
+    pub radius: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Square {
+    #[serde(rename = "length")]
+    pub side: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Shape {
+    Circle(Circle),
+    Square(Square),
+}
+
+impl Shape {
+    pub fn area(&self) -> f64 {
+        match self {
+            Shape::Circle(circle) => std::f64::consts::PI * circle.radius * circle.radius,
+            Shape::Square(square) => square.side * square.side,
+        }
+    }
+}
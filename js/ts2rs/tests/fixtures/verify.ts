@@ -0,0 +1,42 @@
+/**
+ * Types mirrored by hand in verify.rs, for the verify tests
+ */
+
+export interface Job {
+  id: string;
+  title: string;
+  note?: string;
+  retries: number;
+  priority: Priority;
+  status: Status;
+}
+
+export enum Priority {
+  Low = 1,
+  Normal = 2,
+  High = 3,
+}
+
+export enum Status {
+  Active = "active",
+  OnHold = "on-hold",
+  Done = "done",
+}
+
+export interface Task {
+  mode: string;
+}
+
+export class Worker {
+  name: string = "";
+}
+
+export interface Circle {
+  radius: number;
+}
+
+export interface Square {
+  side: number;
+}
+
+export type Shape = Circle | Square;
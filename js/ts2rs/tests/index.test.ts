@@ -4,10 +4,36 @@
 
 import { describe, test, expect } from "bun:test";
 import * as path from "path";
-import { convert, resolveTypes, TypeConversionError } from "../src/index";
+import {
+  clearCache,
+  convert,
+  convertProject,
+  formatDiagnostic,
+  loadConfig,
+  resolveTypes,
+  TypeConversionError,
+  validateConfig,
+  verify,
+  type Diagnostic,
+} from "../src/index";
+import { watchProject, type WatchRun } from "../src/watch";
 
 const fixturesDir = path.join(__dirname, "fixtures");
 const sampleTypesPath = path.join(fixturesDir, "sample-types.ts");
+const mergedInterfacesPath = path.join(fixturesDir, "merged-interfaces.ts");
+const augmentationPath = path.join(fixturesDir, "augmentation", "index.ts");
+const classesPath = path.join(fixturesDir, "classes.ts");
+const constantsPath = path.join(fixturesDir, "constants.ts");
+const namespacesPath = path.join(fixturesDir, "namespaces.ts");
+const collisionsPath = path.join(fixturesDir, "collisions", "index.ts");
+const modulesPath = path.join(fixturesDir, "modules", "index.ts");
+const exportsPath = path.join(fixturesDir, "exports", "index.ts");
+const monorepoAppDir = path.join(fixturesDir, "monorepo", "packages", "app");
+const projectConfigPath = path.join(fixturesDir, "project", "ts2rs.config.json");
+const selectionPath = path.join(fixturesDir, "selection.ts");
+const diagnosticsPath = path.join(fixturesDir, "diagnostics.ts");
+const verifyPath = path.join(fixturesDir, "verify.ts");
+const verifyRustPath = path.join(fixturesDir, "verify.rs");
 
 describe("resolveTypes", () => {
   test("should resolve all exported types from a file", () => {
@@ -172,6 +198,97 @@ describe("convert - Union Types", () => {
   });
 });
 
+describe("convert - Mapped Types", () => {
+  test("should convert Record over a literal union to a struct", async () => {
+    const result = await convert({
+      entryFile: sampleTypesPath,
+      typeNames: ["Translations"],
+    });
+
+    expect(result.rustCode).toContain("pub struct Translations");
+    expect(result.rustCode).toContain('#[serde(rename = "en")]');
+    expect(result.rustCode).toContain("pub en: String");
+    expect(result.rustCode).toContain('#[serde(rename = "de-DE")]');
+    expect(result.rustCode).toContain("pub de_de: String");
+    expect(result.rustCode).not.toContain("HashMap");
+  });
+
+  test("should keep optional modifiers of hand-written mapped types", async () => {
+    const types = resolveTypes({
+      entryFile: sampleTypesPath,
+      typeNames: ["FeatureFlags"],
+    });
+
+    const flags = types.find((t) => t.name === "FeatureFlags");
+    expect(flags?.type.kind).toBe("struct");
+    if (flags?.type.kind !== "struct") return;
+    expect(flags.type.fields.map((f) => f.name)).toEqual(["darkMode", "beta"]);
+    expect(flags.type.fields.every((f) => f.optional && f.readonly && f.exactName)).toBe(true);
+  });
+
+  test("should name inline mapped types after their field and keep open-ended keys as maps", async () => {
+    const result = await convert({
+      entryFile: sampleTypesPath,
+      typeNames: ["LocalizedSettings"],
+    });
+
+    expect(result.rustCode).toContain("pub struct LocalizedSettingsLabels");
+    expect(result.rustCode).toContain("pub labels: LocalizedSettingsLabels");
+    expect(result.rustCode).toContain("pub flags: FeatureFlags");
+    expect(result.rustCode).toContain("pub dark_mode: Option<bool>");
+    expect(result.rustCode).toContain("pub counters: HashMap<String, f64>");
+  });
+
+  test("should suffix the names of inline mapped types taken by declared types", async () => {
+    const result = await convert({
+      entryFile: sampleTypesPath,
+      typeNames: ["LocalizedPage", "LocalizedPageTitle"],
+      strict: true,
+    });
+
+    expect(result.rustCode).toContain("pub struct LocalizedPageTitle {\n    pub fallback: String,");
+    expect(result.rustCode).toContain("pub struct LocalizedPageTitle2 {");
+    expect(result.rustCode).toContain("pub title: LocalizedPageTitle2,");
+  });
+});
+
+describe("convert - Conditional and Template Literal Types", () => {
+  test("should evaluate conditional types with concrete arguments", async () => {
+    const result = await convert({
+      entryFile: sampleTypesPath,
+      typeNames: ["Session"],
+      strict: true,
+    });
+
+    expect(result.rustCode).toContain("pub owner: UserId");
+    expect(result.rustCode).toContain("pub device: String");
+    expect(result.rustCode).toContain("pub token: String");
+    expect(result.rustCode).not.toContain("pub struct User ");
+  });
+
+  test("should convert template literal aliases to String by default", async () => {
+    const result = await convert({
+      entryFile: sampleTypesPath,
+      typeNames: ["UserId"],
+    });
+
+    expect(result.rustCode).toContain("pub type UserId = String;");
+  });
+
+  test("should generate validated newtypes for template literal aliases when enabled", async () => {
+    const result = await convert({
+      entryFile: sampleTypesPath,
+      typeNames: ["UserId"],
+      templateLiteralNewtypes: true,
+    });
+
+    expect(result.rustCode).toContain('#[serde(try_from = "String", into = "String")]');
+    expect(result.rustCode).toContain("pub struct UserId(String);");
+    expect(result.rustCode).toContain('&[TemplateLiteralPart::Text("usr-"), TemplateLiteralPart::Number],');
+    expect(result.rustCode).toContain("fn matches_template_literal(");
+  });
+});
+
 describe("convert - Interface Inheritance", () => {
   test("should include fields from extended interfaces", async () => {
     const result = await convert({
@@ -190,6 +307,884 @@ describe("convert - Interface Inheritance", () => {
   });
 });
 
+describe("convert - Declaration Merging", () => {
+  test("should combine fields of merged and augmented interface declarations", async () => {
+    const result = await convert({
+      entryFile: mergedInterfacesPath,
+      typeNames: ["Account"],
+    });
+
+    expect(result.rustCode).toContain("/// An account in the billing system");
+    expect(result.rustCode).toContain("pub id: String");
+    expect(result.rustCode).toContain("pub email: String");
+    expect(result.rustCode).toContain("pub plan: Option<String>");
+    expect(result.rustCode.match(/pub id: String/g)?.length).toBe(1);
+  });
+
+  test("should merge augmentations declared in files the entry file doesn't import", async () => {
+    const result = await convert({
+      entryFile: augmentationPath,
+      typeNames: ["Account"],
+      strict: true,
+    });
+
+    expect(result.rustCode).toContain("pub id: String");
+    expect(result.rustCode).toContain("pub plan: Option<String>");
+  });
+
+  test("should add the fields of a base extended by several merged declarations once", async () => {
+    const result = await convert({
+      entryFile: mergedInterfacesPath,
+      typeNames: ["Invoice"],
+      strict: true,
+    });
+
+    expect(result.rustCode.match(/pub created_at: String/g)?.length).toBe(1);
+    expect(result.rustCode).toContain("pub total: f64");
+    // The redeclared property replaces the base's
+    expect(result.rustCode.match(/pub updated_by: /g)?.length).toBe(1);
+    expect(result.rustCode).toContain("pub updated_by: String");
+  });
+
+  test("should throw when merged declarations conflict", () => {
+    expect(() => {
+      resolveTypes({
+        entryFile: mergedInterfacesPath,
+        typeNames: ["Conflicting"],
+      });
+    }).toThrow(/Conflicting declarations of property 'value'/);
+  });
+});
+
+describe("convert - Classes", () => {
+  test("should convert public instance properties of classes and their base classes", async () => {
+    const result = await convert({
+      entryFile: classesPath,
+      typeNames: ["UserDto"],
+      strict: true,
+    });
+
+    expect(result.rustCode).toContain("pub struct UserDto");
+    expect(result.rustCode).toContain("pub id: String");
+    expect(result.rustCode).toContain("pub created_at: String");
+    expect(result.rustCode).toContain("pub name: String");
+    expect(result.rustCode).toContain("pub role: String");
+    expect(result.rustCode).toContain("pub nickname: Option<String>");
+    expect(result.rustCode).not.toContain("secret");
+    expect(result.rustCode).not.toContain("internal_note");
+    expect(result.rustCode).not.toContain("hidden");
+    expect(result.rustCode).not.toContain("version");
+    expect(result.rustCode).not.toContain("on_change");
+    expect(result.rustCode).not.toContain("display_name");
+    expect(result.rustCode).not.toContain("greet");
+  });
+
+  test("should turn property initializers into serde defaults", async () => {
+    const result = await convert({
+      entryFile: classesPath,
+      typeNames: ["UserDto"],
+    });
+
+    expect(result.rustCode).toContain('#[serde(default = "UserDto::default_age")]');
+    expect(result.rustCode).toContain("fn default_age() -> f64 {\n        18.0\n    }");
+    expect(result.rustCode).toContain('fn default_role() -> String {\n        "member".to_string()\n    }');
+    expect(result.rustCode).toContain("fn default_active() -> bool {\n        true\n    }");
+    expect(result.rustCode).toContain('#[serde(default = "UserDto::default_created_at")]');
+    expect(result.rustCode).toMatch(/#\[serde\(default\)\]\n\s+pub tags: Vec<String>/);
+  });
+
+  test("should call the default functions of generic classes with their type parameters", async () => {
+    const result = await convert({
+      entryFile: classesPath,
+      typeNames: ["PageDto"],
+    });
+
+    expect(result.rustCode).toContain("pub struct PageDto<T> {");
+    expect(result.rustCode).toContain('#[serde(default = "PageDto::<T>::default_size")]');
+    expect(result.rustCode).toContain("impl<T> PageDto<T> {\n    fn default_size() -> f64 {");
+  });
+
+  test("should only turn null initializers into defaults for nullable properties", async () => {
+    const result = await convert({
+      entryFile: classesPath,
+      typeNames: ["PageDto"],
+    });
+
+    expect(result.rustCode).toMatch(/#\[serde\(default\)\]\n\s+pub cursor: Option<String>/);
+    expect(result.rustCode).toContain("    pub cursor: Option<String>,\n    pub label: String,");
+  });
+
+  test("should replace inherited properties redeclared by a subclass", async () => {
+    const result = await convert({
+      entryFile: classesPath,
+      typeNames: ["AdminDto"],
+      strict: true,
+    });
+
+    const start = result.rustCode.indexOf("pub struct AdminDto");
+    const adminDto = result.rustCode.slice(start, result.rustCode.indexOf("\n}", start));
+    expect(adminDto.match(/pub nickname:/g)?.length).toBe(1);
+    expect(adminDto).toContain('#[serde(default = "AdminDto::default_nickname")]\n    pub nickname: String,');
+    expect(adminDto).toContain("pub role: String,");
+  });
+
+  test("should only turn empty object initializers into defaults for types with a default", async () => {
+    const result = await convert({
+      entryFile: classesPath,
+      typeNames: ["PageDto"],
+    });
+
+    expect(result.rustCode).toContain("    pub label: String,\n    pub settings: PageSettings,");
+  });
+
+  test("should include non-public members when configured", async () => {
+    const result = await convert({
+      entryFile: classesPath,
+      typeNames: ["UserDto"],
+      classMemberVisibility: "all",
+    });
+
+    expect(result.rustCode).toContain("pub secret: Option<String>");
+    expect(result.rustCode).toContain("pub internal_note: String");
+    expect(result.rustCode).not.toContain("hidden");
+  });
+});
+
+describe("convert - Namespaces", () => {
+  test("should collect namespace members under their qualified names", () => {
+    const types = resolveTypes({ entryFile: namespacesPath });
+    const typeNames = types.map((t) => t.name);
+
+    expect(typeNames).toContain("Api.User");
+    expect(typeNames).toContain("Api.Role");
+    expect(typeNames).toContain("Api.V1.User");
+    expect(typeNames).toContain("Admin.User");
+    expect(typeNames).toContain("ApiUser");
+  });
+
+  test("should emit namespaces as nested modules", async () => {
+    const result = await convert({
+      entryFile: namespacesPath,
+      typeNames: ["Session"],
+      strict: true,
+    });
+
+    expect(result.rustCode).toContain("pub user: api::User,");
+    expect(result.rustCode).toContain("pub admin: Option<admin::User>,");
+    expect(result.rustCode).toContain("pub legacy: Option<api::v1::User>,");
+    expect(result.rustCode).toContain("pub mod api {\n    use super::*;");
+    expect(result.rustCode).toContain("pub team: super::Team,");
+    expect(result.rustCode).toContain("pub role: Role,");
+    expect(result.rustCode).toContain("pub mod v1 {\n        use super::super::*;");
+    expect(result.rustCode).toContain("pub current: super::User,");
+    expect(result.rustCode).toContain("pub mod admin {");
+    expect(result.rustCode.match(/pub struct User \{/g)?.length).toBe(3);
+  });
+
+  test("should prefix namespace members with unique names in flat mode", async () => {
+    const result = await convert({
+      entryFile: namespacesPath,
+      typeNames: ["Session", "ApiUser"],
+      namespaces: "flat",
+    });
+
+    expect(result.rustCode).toContain("pub struct ApiUser {");
+    expect(result.rustCode).toContain("pub struct ApiUser2 {");
+    expect(result.rustCode).toContain("pub enum ApiRole {");
+    expect(result.rustCode).toContain("pub user: ApiUser2,");
+    expect(result.rustCode).toContain("pub admin: Option<AdminUser>,");
+    expect(result.rustCode).toContain("pub legacy: Option<ApiV1User>,");
+    expect(result.rustCode).toContain("pub current: ApiUser2,");
+    expect(result.rustCode).toContain("pub role: ApiRole,");
+    expect(result.rustCode).not.toContain("pub mod");
+  });
+});
+
+describe("convert - Type Selection", () => {
+  test("should select exported types with patterns", async () => {
+    const result = await convert({
+      entryFile: selectionPath,
+      typeNames: ["Api*", "*Request|*Response", "!*Internal"],
+      strict: true,
+    });
+
+    expect(result.rustCode).toContain("pub struct ApiUser {");
+    expect(result.rustCode).toContain("pub struct CreateUserRequest {");
+    expect(result.rustCode).toContain("pub struct CreateUserResponse {");
+    expect(result.rustCode).toContain("pub struct Profile {");
+    expect(result.rustCode).not.toContain("CacheEntryInternal");
+    expect(result.rustCode).not.toContain("Helper");
+
+    expect(result.selection).toContainEqual({ name: "ApiUser", sourceFile: selectionPath, reason: "pattern", detail: "Api*" });
+    expect(result.selection).toContainEqual({
+      name: "CreateUserResponse",
+      sourceFile: selectionPath,
+      reason: "pattern",
+      detail: "*Request|*Response",
+    });
+    expect(result.selection).toContainEqual({ name: "Profile", sourceFile: selectionPath, reason: "dependency", detail: "ApiUser" });
+  });
+
+  test("should select every export but the excluded ones", async () => {
+    const result = await convert({ entryFile: selectionPath, typeNames: ["!*Internal", "!Helper"] });
+
+    expect(result.rustCode).toContain("pub struct ApiUser {");
+    expect(result.rustCode).toContain("pub struct AuditEntry {");
+    expect(result.rustCode).not.toContain("CacheEntryInternal");
+    expect(result.rustCode).not.toContain("Helper");
+    expect(result.selection).toContainEqual({ name: "ApiUser", sourceFile: selectionPath, reason: "exported" });
+  });
+
+  test("should only filter the listed names with excluding patterns", async () => {
+    const result = await convert({ entryFile: selectionPath, typeNames: ["ApiUser", "CreateUserRequest", "!Create*"] });
+
+    expect(result.rustCode).toContain("pub struct ApiUser {");
+    expect(result.rustCode).toContain("pub struct Profile {");
+    expect(result.rustCode).not.toContain("CreateUserRequest");
+    expect(result.rustCode).not.toContain("pub struct AuditEntry {");
+    expect(result.rustCode).not.toContain("CacheEntryInternal");
+    expect(result.selection?.map((s) => s.name).sort()).toEqual(["ApiUser", "Audit", "Profile"]);
+  });
+
+  test("should keep @ts2rs-exclude types out, even when referenced", async () => {
+    const result = await convert({ entryFile: selectionPath, typeNames: ["ApiUser", "CreateUserRequest"], strict: true });
+
+    expect(result.rustCode).not.toContain("pub struct Audit {");
+    expect(result.rustCode).not.toContain("AuditEntry");
+    expect(result.rustCode).toContain("pub audit: Value,");
+    expect(result.rustCode).not.toContain("pub struct Timestamp {");
+    expect(result.rustCode).toContain("pub created_at: chrono::DateTime<chrono::Utc>,");
+
+    expect(result.selection).toContainEqual({ name: "ApiUser", sourceFile: selectionPath, reason: "listed" });
+    expect(result.selection).toContainEqual({
+      name: "Audit",
+      sourceFile: selectionPath,
+      reason: "excluded",
+      detail: "serde_json::Value",
+    });
+    expect(result.selection).toContainEqual({
+      name: "Timestamp",
+      sourceFile: selectionPath,
+      reason: "excluded",
+      detail: "chrono::DateTime<chrono::Utc>",
+    });
+  });
+
+  test("should not write anything in a dry run", async () => {
+    const fs = await import("fs");
+    const outputPath = path.join(fixturesDir, "dry-run.rs");
+
+    const result = await convert({ entryFile: selectionPath, typeNames: ["Api*"], outputPath, dryRun: true });
+
+    expect(fs.existsSync(outputPath)).toBe(false);
+    expect(result.selection?.map((s) => s.name)).toEqual(["ApiUser", "Profile", "Audit"]);
+  });
+});
+
+describe("convert - Exports", () => {
+  test("should name types after the names the entry file exports them under", async () => {
+    const result = await convert({ entryFile: exportsPath, strict: true });
+
+    expect(result.rustCode).toContain("pub struct User {");
+    expect(result.rustCode).not.toContain("InternalUser");
+    expect(result.rustCode).toContain("pub members: Vec<User>,");
+    expect(result.rustCode).toContain("pub owner: User,");
+    expect(result.rustCode).toContain("pub enum Visibility {");
+    expect(result.rustCode).toContain("pub struct Project {");
+    expect(result.rustCode).toContain("pub struct Account {");
+    expect(result.rustCode).toContain("pub struct Settings {");
+    expect(result.rustCode).toContain("pub mod billing {");
+    expect(result.rustCode).toContain("pub struct Invoice {");
+  });
+
+  test("should select types by their exported name", async () => {
+    const result = await convert({
+      entryFile: exportsPath,
+      typeNames: ["User", "billing.Invoice"],
+      strict: true,
+    });
+
+    // Not the unrelated `User` of another fixture file
+    expect(result.rustCode).toContain("pub struct User {\n    pub id: String,\n    pub name: String,\n}");
+    expect(result.rustCode).toContain("pub mod billing {");
+    expect(result.convertedTypes).toEqual(["User", "billing.Invoice"]);
+  });
+});
+
+describe("convert - Module Resolution", () => {
+  test("should resolve tsconfig paths, workspace packages and project references", async () => {
+    const result = await convert({
+      entryFile: path.join(monorepoAppDir, "src", "index.ts"),
+      tsConfigPath: path.join(monorepoAppDir, "tsconfig.app.json"),
+      strict: true,
+    });
+
+    // `@app/theme` through `paths` of a tsconfig extending another
+    expect(result.rustCode).toContain("pub enum Theme {");
+    expect(result.rustCode).toContain("pub theme: Theme,");
+    // `@acme/shared` through the `exports` of a referenced project, whose unbuilt `dist` maps to its sources
+    expect(result.rustCode).toContain("pub enum UserRole {");
+    expect(result.rustCode).toContain("pub permissions: Vec<Permission>,");
+    expect(result.rustCode).toContain("pub struct Permission {");
+    // `@acme/legacy` through the `.d.ts` output of a referenced project
+    expect(result.rustCode).toContain("pub legacy: LegacyRecord,");
+    expect(result.rustCode).toContain("pub struct LegacyRecord {");
+  });
+
+  test("should not resolve the aliases without the project's tsconfig", async () => {
+    await expect(
+      convert({ entryFile: path.join(monorepoAppDir, "src", "index.ts"), strict: true }),
+    ).rejects.toThrow(TypeConversionError);
+  });
+
+  test("should reject a missing tsconfig", async () => {
+    await expect(
+      convert({ entryFile: sampleTypesPath, tsConfigPath: path.join(fixturesDir, "missing.json") }),
+    ).rejects.toThrow("tsconfig not found");
+  });
+});
+
+describe("convert - Project Loading", () => {
+  test("should only load the files reachable from the entry file", async () => {
+    const result = await convert({ entryFile: collisionsPath, verbose: true });
+
+    // The entry file and the two files it imports, plus the tsconfig's file augmenting a module (and the module
+    // it augments), not the rest of the tsconfig's files
+    expect(result.stats?.sourceFiles).toBe(5);
+    expect(result.stats?.types).toBeGreaterThan(0);
+    expect(result.stats?.loadMs).toBeGreaterThanOrEqual(0);
+    expect(result.stats?.resolveMs).toBeGreaterThanOrEqual(0);
+  });
+
+  test("should not collect stats unless verbose", async () => {
+    const result = await convert({ entryFile: collisionsPath });
+
+    expect(result.stats).toBeUndefined();
+  });
+});
+
+describe("convertProject", () => {
+  test("should run the jobs of a config file, generating shared types once", async () => {
+    const fs = await import("fs");
+    const projectDir = path.dirname(projectConfigPath);
+    const config = await loadConfig(projectConfigPath);
+
+    const jobs = await convertProject(config, projectDir);
+    expect(jobs.map((job) => job.name)).toEqual(["orders", "invoices"]);
+
+    const orders = fs.readFileSync(path.join(projectDir, "out", "src", "orders.rs"), "utf-8");
+    expect(orders).toContain("pub struct Order {");
+    expect(orders).toContain("pub struct Money {");
+    expect(orders).toContain("#[non_exhaustive]");
+
+    // `Money` is referenced from the output of the first job
+    const invoices = fs.readFileSync(path.join(projectDir, "out", "src", "invoices.rs"), "utf-8");
+    expect(invoices).toContain("pub struct Invoice {");
+    expect(invoices).toContain("pub amount: crate::orders::Money,");
+    expect(invoices).not.toContain("pub struct Money {");
+    // Job options replace the shared ones
+    expect(invoices).toContain("#[derive(Default)]");
+    expect(invoices).not.toContain("#[non_exhaustive]");
+    expect(jobs[1]!.result.externalTypes).toEqual([{ rustPath: "crate::orders::Money", usedBy: ["Invoice"] }]);
+
+    fs.rmSync(path.join(projectDir, "out"), { recursive: true });
+  });
+
+  test("should report every problem of an invalid config", () => {
+    expect(validateConfig({ jobs: [] })).toEqual(["jobs must be a non-empty array"]);
+    expect(
+      validateConfig({
+        strict: "yes",
+        lints: { "value-fallback": "error", unused: "warn" },
+        jobs: [
+          { entryFile: 1, outputPath: "types.rs", strcit: true },
+          { entryFile: "b.ts", outputPath: "types.rs", namespaces: "nested", customTypeMappings: { Date: { rust: "String" } } },
+          { entryFile: "c.ts" },
+        ],
+      }),
+    ).toEqual([
+      "strict must be a boolean",
+      "lints[\"value-fallback\"] must be one of \"allow\", \"warn\", \"deny\"",
+      "lints[\"unused\"] is not a lint, expected one of value-fallback, anonymous-struct, untagged-union, dropped-methods, lossy-number, type-collision",
+      "jobs[0].entryFile must be a string",
+      "jobs[0].strcit is not a known option",
+      "jobs[1].namespaces must be one of \"modules\", \"flat\"",
+      "jobs[1].customTypeMappings[\"Date\"].rust is not a known option",
+      "jobs[1].customTypeMappings[\"Date\"].rustType is required",
+      "jobs[1] writes to types.rs, like jobs[0]",
+      "jobs[2] needs an outputPath or an outputDir",
+    ]);
+  });
+});
+
+describe("convert - Cache", () => {
+  test("should reuse unchanged types and match a conversion without the cache", async () => {
+    const fs = await import("fs");
+    const os = await import("os");
+    const projectDir = fs.mkdtempSync(path.join(os.tmpdir(), "ts2rs-cache-"));
+    const cacheDir = path.join(projectDir, ".cache");
+    fs.cpSync(path.dirname(collisionsPath), projectDir, { recursive: true });
+    const entryFile = path.join(projectDir, "index.ts");
+
+    const cold = await convert({ entryFile, cacheDir, verbose: true });
+    expect(cold.stats?.reusedTypes).toBe(0);
+
+    // Nothing changed, the project isn't loaded
+    const warm = await convert({ entryFile, cacheDir, verbose: true });
+    expect(warm.rustCode).toBe(cold.rustCode);
+    expect(warm.warnings).toEqual(cold.warnings);
+    expect(warm.stats?.sourceFiles).toBe(0);
+    expect(warm.stats?.reusedTypes).toBe(warm.stats?.types);
+
+    // Only the changed file and the entry file importing it are resolved again
+    fs.writeFileSync(path.join(projectDir, "billing", "status.ts"), 'export type Status = "pending" | "paid";\n');
+    const incremental = await convert({ entryFile, cacheDir, verbose: true });
+    const uncached = await convert({ entryFile });
+    expect(incremental.rustCode).toBe(uncached.rustCode);
+    expect(incremental.rustCode).not.toContain("Refunded");
+    expect(incremental.stats?.reusedTypes).toBe(1);
+
+    fs.rmSync(projectDir, { recursive: true });
+  });
+
+  test("should not reuse the result when a file augmenting a cached type is added", async () => {
+    const fs = await import("fs");
+    const os = await import("os");
+    const projectDir = fs.mkdtempSync(path.join(os.tmpdir(), "ts2rs-cache-"));
+    const cacheDir = path.join(projectDir, ".cache");
+    fs.cpSync(path.dirname(augmentationPath), projectDir, { recursive: true });
+    const entryFile = path.join(projectDir, "index.ts");
+    const planPath = path.join(projectDir, "plan.ts");
+    const plan = fs.readFileSync(planPath, "utf-8");
+    fs.rmSync(planPath);
+
+    const cold = await convert({ entryFile, cacheDir, typeNames: ["Account"] });
+    expect(cold.rustCode).not.toContain("pub plan");
+
+    fs.writeFileSync(planPath, plan);
+    const warm = await convert({ entryFile, cacheDir, typeNames: ["Account"], verbose: true });
+    expect(warm.stats?.sourceFiles).toBeGreaterThan(0);
+    expect(warm.rustCode).toBe((await convert({ entryFile, typeNames: ["Account"] })).rustCode);
+    expect(warm.rustCode).toContain("pub plan: Option<String>");
+
+    fs.rmSync(projectDir, { recursive: true });
+  });
+
+  test("should clear the cache", async () => {
+    const fs = await import("fs");
+    const os = await import("os");
+    const cacheDir = fs.mkdtempSync(path.join(os.tmpdir(), "ts2rs-cache-"));
+
+    await convert({ entryFile: collisionsPath, cacheDir });
+    expect(fs.readdirSync(cacheDir).length).toBe(1);
+    clearCache(cacheDir);
+    expect(fs.existsSync(cacheDir)).toBe(false);
+  });
+});
+
+describe("convert - Check", () => {
+  test("should name the source file relative to the project", async () => {
+    const result = await convert({ entryFile: sampleTypesPath, typeNames: ["Person"] });
+
+    expect(result.rustCode).toContain("// Source: tests/fixtures/sample-types.ts\n");
+  });
+
+  test("should report output files that differ from the generated code", async () => {
+    const fs = await import("fs");
+    const os = await import("os");
+    const outputDir = fs.mkdtempSync(path.join(os.tmpdir(), "ts2rs-check-"));
+    const outputPath = path.join(outputDir, "types.rs");
+
+    const missing = await convert({ entryFile: collisionsPath, outputPath, checkOnly: true });
+    expect(fs.existsSync(outputPath)).toBe(false);
+    expect(missing.drift?.length).toBe(1);
+    expect(missing.drift?.[0]?.diff).toStartWith("--- /dev/null\n");
+
+    await convert({ entryFile: collisionsPath, outputPath });
+    const upToDate = await convert({ entryFile: collisionsPath, outputPath, checkOnly: true });
+    expect(upToDate.drift).toEqual([]);
+
+    // The generator line is ignored, but not the source file
+    const generated = fs.readFileSync(outputPath, "utf-8");
+    fs.writeFileSync(outputPath, generated.replace(/^\/\/ This file is auto-generated by ts2rs.*$/m, "// This file is auto-generated by ts2rs 0.1.0"));
+    expect((await convert({ entryFile: collisionsPath, outputPath, checkOnly: true })).drift).toEqual([]);
+    fs.writeFileSync(outputPath, generated.replace(/^\/\/ Source: .*$/m, "// Source: elsewhere/index.ts"));
+    expect((await convert({ entryFile: collisionsPath, outputPath, checkOnly: true })).drift?.[0]?.diff).toMatch(
+      /^-\/\/ Source: elsewhere\/index.ts$/m,
+    );
+
+    fs.writeFileSync(outputPath, generated.replace("pub payment: billing::Status,", "pub payment: String,"));
+    const outdated = await convert({ entryFile: collisionsPath, outputPath, checkOnly: true });
+    expect(outdated.drift?.length).toBe(1);
+    expect(outdated.drift?.[0]?.path).toBe(outputPath);
+    expect(outdated.drift?.[0]?.diff).toMatch(/^-\s+pub payment: String,$/m);
+    expect(outdated.drift?.[0]?.diff).toMatch(/^\+\s+pub payment: billing::Status,$/m);
+    expect(fs.readFileSync(outputPath, "utf-8")).toContain("pub payment: String,");
+
+    fs.rmSync(outputDir, { recursive: true });
+  });
+});
+
+describe("watchProject", () => {
+  test("should not rewrite unchanged output files", async () => {
+    const fs = await import("fs");
+    const os = await import("os");
+    const outputDir = fs.mkdtempSync(path.join(os.tmpdir(), "ts2rs-watch-"));
+    const outputPath = path.join(outputDir, "types.rs");
+
+    expect((await convert({ entryFile: collisionsPath, outputPath })).writtenFiles).toEqual([outputPath]);
+    expect((await convert({ entryFile: collisionsPath, outputPath })).writtenFiles).toEqual([]);
+
+    fs.rmSync(outputDir, { recursive: true });
+  });
+
+  test("should convert again when a file the types were resolved from changes", async () => {
+    const fs = await import("fs");
+    const os = await import("os");
+    const projectDir = fs.realpathSync(fs.mkdtempSync(path.join(os.tmpdir(), "ts2rs-watch-")));
+    fs.cpSync(path.dirname(collisionsPath), projectDir, { recursive: true });
+    const entryFile = path.join(projectDir, "index.ts");
+    const statusPath = path.join(projectDir, "billing", "status.ts");
+    const outputPath = path.join(projectDir, "out", "types.rs");
+
+    const runs: WatchRun[] = [];
+    let onRun: (() => void) | undefined;
+    const watcher = await watchProject({ jobs: [{ entryFile, outputPath }] }, projectDir, {}, (run) => {
+      runs.push(run);
+      onRun?.();
+    });
+    expect(runs.length).toBe(1);
+    expect(runs[0]?.results[0]?.result.inputFiles).toContain(statusPath);
+    expect(fs.readFileSync(outputPath, "utf-8")).toContain("Refunded");
+
+    const nextRun = new Promise<void>((resolve) => {
+      onRun = resolve;
+    });
+    fs.writeFileSync(statusPath, 'export type Status = "pending" | "paid";\n');
+    await nextRun;
+    watcher.close();
+
+    expect(runs[1]?.changedFiles).toEqual([statusPath]);
+    expect(runs[1]?.errors).toEqual([]);
+    expect(fs.readFileSync(outputPath, "utf-8")).not.toContain("Refunded");
+
+    fs.rmSync(projectDir, { recursive: true });
+  });
+});
+
+describe("convert - Name Collisions", () => {
+  test("should disambiguate same-named types from different files", async () => {
+    const result = await convert({ entryFile: collisionsPath });
+
+    expect(result.rustCode).toContain("pub struct Status {");
+    expect(result.rustCode).toContain("pub mod billing {");
+    expect(result.rustCode).toContain("pub enum Status {");
+    expect(result.rustCode).toContain("pub delivery: Status,");
+    expect(result.rustCode).toContain("pub payment: billing::Status,");
+    expect(result.warnings.some((w) => w.code === "TS2RS003" && w.message.startsWith("Types named 'Status' are declared in several files"))).toBe(true);
+  });
+
+  test("should reject same-named types in strict mode", async () => {
+    await expect(
+      convert({ entryFile: collisionsPath, strict: true }),
+    ).rejects.toThrow(TypeConversionError);
+  });
+
+  test("should name types from typeRenames", async () => {
+    const result = await convert({
+      entryFile: collisionsPath,
+      strict: true,
+      typeRenames: { "billing/status.ts#Status": "PaymentStatus" },
+    });
+
+    expect(result.rustCode).toContain("pub struct Status {");
+    expect(result.rustCode).toContain("pub enum PaymentStatus {");
+    expect(result.rustCode).toContain("pub payment: PaymentStatus,");
+    expect(result.rustCode).not.toContain("pub mod");
+  });
+});
+
+describe("convert - Diagnostics", () => {
+  test("should locate warnings at the field they are about", async () => {
+    const result = await convert({ entryFile: diagnosticsPath });
+
+    expect(result.warnings).toEqual([
+      {
+        code: "TS2RS001",
+        name: "value-fallback",
+        severity: "warning",
+        message: 'Falling back to serde_json::Value: Inline literal union cannot be converted (must be a named type) (type: "low" | "high")',
+        file: diagnosticsPath,
+        line: 6,
+        column: 3,
+        endLine: 6,
+        endColumn: 28,
+        typeName: "Job",
+        fieldPath: "priority",
+        suggestion: "give the type a name, or map it to a Rust type with customTypeMappings",
+      },
+    ]);
+    expect(formatDiagnostic(result.warnings[0]!)).toContain(
+      '6 |   priority: "low" | "high";\n  |   ^^^^^^^^^^^^^^^^^^^^^^^^^\n',
+    );
+  });
+
+  test("should locate errors in strict mode", async () => {
+    const error = await convert({ entryFile: diagnosticsPath, strict: true }).catch((e) => e);
+
+    expect(error).toBeInstanceOf(TypeConversionError);
+    expect(error.line).toBe(6);
+    expect(error.diagnostic).toMatchObject({
+      code: "TS2RS001",
+      severity: "error",
+      line: 6,
+      column: 3,
+      typeName: "Job",
+      fieldPath: "priority",
+    });
+  });
+
+  test("should report lints at their configured levels", async () => {
+    const result = await convert({
+      entryFile: diagnosticsPath,
+      lints: { "value-fallback": "allow", "untagged-union": "warn", "dropped-methods": "warn" },
+    });
+
+    expect(result.warnings.map((w) => `${w.name} ${w.typeName}`).sort()).toEqual([
+      "dropped-methods Worker",
+      "untagged-union Shape",
+    ]);
+  });
+
+  test("should report denied lints together, except where allowed with @ts2rs-allow", async () => {
+    const error = await convert({
+      entryFile: diagnosticsPath,
+      lints: { "value-fallback": "deny", "dropped-methods": "deny" },
+    }).catch((e) => e);
+
+    expect(error).toBeInstanceOf(TypeConversionError);
+    expect(error.message).toContain("(and 1 more error(s))");
+    expect(error.errors.map((e: Diagnostic) => `${e.severity} ${e.name} ${e.typeName}`).sort()).toEqual([
+      "error dropped-methods Worker",
+      "error value-fallback Job",
+    ]);
+  });
+});
+
+describe("convert - Manifest", () => {
+  test("should write the declaration each item was converted from next to the output", async () => {
+    const fs = await import("fs");
+    const os = await import("os");
+    const outputDir = fs.mkdtempSync(path.join(os.tmpdir(), "ts2rs-manifest-"));
+    const outputPath = path.join(outputDir, "types.rs");
+
+    const result = await convert({ entryFile: diagnosticsPath, outputPath, outputModulePath: "crate::jobs", manifest: true });
+
+    const manifestPath = path.join(outputDir, "types.manifest.json");
+    expect(result.writtenFiles).toEqual([outputPath, manifestPath]);
+    expect(JSON.parse(fs.readFileSync(manifestPath, "utf-8"))).toEqual(result.manifest);
+    expect(result.manifest?.items.find((item) => item.tsName === "Job")).toEqual({
+      tsName: "Job",
+      sourceFile: "tests/fixtures/diagnostics.ts",
+      line: 4,
+      rustPath: "crate::jobs::Job",
+      kind: "struct",
+      fields: [
+        { tsName: "id", rustName: "id", rustType: "String", optional: false },
+        { tsName: "priority", rustName: "priority", rustType: "Value", optional: false },
+      ],
+      fallbacks: [
+        { path: "priority", reason: 'Inline literal union cannot be converted (must be a named type) (type: "low" | "high")' },
+      ],
+    });
+    expect(result.manifest?.items.find((item) => item.tsName === "Shape")?.variants).toEqual([
+      { rustName: "Circle", rustType: "Circle" },
+      { rustName: "Square", rustType: "Square" },
+    ]);
+
+    fs.rmSync(outputDir, { recursive: true });
+  });
+});
+
+describe("convert - Source Comments", () => {
+  test("should comment items and fields with where they are declared, and fallbacks with their reason", async () => {
+    const result = await convert({ entryFile: diagnosticsPath, sourceComments: true });
+
+    expect(result.rustCode).toMatch(/^\/\/ from tests\/fixtures\/diagnostics.ts:4 \(Job\)$/m);
+    expect(result.rustCode).toContain("    // from tests/fixtures/diagnostics.ts:5 (Job.id)\n    pub id: String,");
+    expect(result.rustCode).toContain(
+      "    // from tests/fixtures/diagnostics.ts:6 (Job.priority)\n" +
+        '    // serde_json::Value: Inline literal union cannot be converted (must be a named type) (type: "low" | "high")\n' +
+        "    pub priority: Value,",
+    );
+  });
+
+  test("should not comment the output by default", async () => {
+    const result = await convert({ entryFile: diagnosticsPath });
+
+    expect(result.rustCode).not.toContain("// from ");
+  });
+});
+
+describe("convert - Kept Regions", () => {
+  test("should keep the hand-written code of the regions when generating again", async () => {
+    const fs = await import("fs");
+    const os = await import("os");
+    const outputDir = fs.mkdtempSync(path.join(os.tmpdir(), "ts2rs-keep-"));
+    const outputPath = path.join(outputDir, "types.rs");
+
+    await convert({ entryFile: diagnosticsPath, outputPath, keepRegions: true });
+    const generated = fs.readFileSync(outputPath, "utf-8");
+    expect(generated).toContain("// ts2rs:keep-start Job\n// ts2rs:keep-end\n");
+    expect(generated).toMatch(/\/\/ ts2rs:keep-start\n\/\/ ts2rs:keep-end\n$/);
+
+    fs.writeFileSync(
+      outputPath,
+      generated
+        .replace("// ts2rs:keep-start Job\n", "// ts2rs:keep-start Job\nimpl Job {}\n")
+        .replace("// ts2rs:keep-start\n", "// ts2rs:keep-start\nfn helper() {}\n// ts2rs:keep-end\n// ts2rs:keep-start Removed\nimpl Removed {}\n"),
+    );
+    const result = await convert({ entryFile: diagnosticsPath, outputPath, keepRegions: true });
+
+    const regenerated = fs.readFileSync(outputPath, "utf-8");
+    expect(regenerated).toContain("// ts2rs:keep-start Job\nimpl Job {}\n// ts2rs:keep-end\n");
+    expect(regenerated).toContain("// ts2rs:keep-start\nfn helper() {}\n// ts2rs:keep-end\n");
+    expect(regenerated).toMatch(/\/\/ ts2rs:keep-start Removed\nimpl Removed \{\}\n\/\/ ts2rs:keep-end\n$/);
+    expect(result.warnings.filter((w) => w.name === "orphaned-region").map((w) => w.message)).toEqual([
+      "The code kept after 'Removed' has no place in the generated code anymore, it was moved to the end of the file",
+    ]);
+    expect((await convert({ entryFile: diagnosticsPath, outputPath, keepRegions: true, checkOnly: true })).drift).toEqual([]);
+
+    fs.rmSync(outputDir, { recursive: true });
+  });
+
+  test("should fail on a region that isn't closed", async () => {
+    const fs = await import("fs");
+    const os = await import("os");
+    const outputDir = fs.mkdtempSync(path.join(os.tmpdir(), "ts2rs-keep-"));
+    const outputPath = path.join(outputDir, "types.rs");
+    fs.writeFileSync(outputPath, "// ts2rs:keep-start Job\nimpl Job {}\n");
+
+    await expect(convert({ entryFile: diagnosticsPath, outputPath, keepRegions: true })).rejects.toThrow(
+      `${outputPath}:1: ts2rs:keep-start without a ts2rs:keep-end`,
+    );
+    expect(fs.readFileSync(outputPath, "utf-8")).toBe("// ts2rs:keep-start Job\nimpl Job {}\n");
+
+    fs.rmSync(outputDir, { recursive: true });
+  });
+});
+
+describe("verify", () => {
+  test("should report how the hand-written Rust types differ from the TypeScript types", async () => {
+    const result = await verify({ entryFile: verifyPath }, verifyRustPath);
+
+    expect(result.verifiedTypes.sort()).toEqual(["Circle", "Job", "Priority", "Shape", "Square", "Status", "Task"]);
+    expect(result.mismatches.map((m) => `${m.code} ${m.typeName}.${m.fieldPath ?? ""}:${m.line ?? ""} ${m.message}`).sort()).toEqual([
+      "TS2RS201 Worker.: No struct or enum named 'Worker' is declared",
+      "TS2RS202 Job.title:7 Field 'title' is missing",
+      "TS2RS203 Task.retries:34 Field 'retries' isn't declared in TypeScript",
+      "TS2RS204 Job.note:9 Field 'note' is optional in TypeScript but required in Rust",
+      "TS2RS204 Job.retries:10 Field 'retries' is required in TypeScript but optional in Rust",
+      "TS2RS205 Square.side:45 Field 'side' is serialized as 'length' by 'side'",
+      "TS2RS206 Job.id:8 Field 'id' is a string in TypeScript but u64 in Rust",
+      // serde_repr enums are serialized as their discriminants, counting up from the previous one
+      "TS2RS207 Priority.:17 Value '3' has no variant",
+      "TS2RS207 Priority.:20 Variant 'Urgent' is serialized as '5', which isn't a value in TypeScript",
+      // Other enums as their variant names, renamed with rename_all
+      "TS2RS207 Status.:25 Value 'done' has no variant",
+      "TS2RS207 Status.:28 Variant 'Finished' is serialized as 'finished', which isn't a value in TypeScript",
+    ]);
+    expect(result.mismatches.every((m) => m.severity === "error" && m.file === verifyRustPath)).toBe(true);
+    expect(result.mismatches.find((m) => m.fieldPath === "id")?.column).toBe(5);
+  });
+
+  test("should accept Rust types serialized like the TypeScript types", async () => {
+    const fs = await import("fs");
+    const os = await import("os");
+    const outputDir = fs.mkdtempSync(path.join(os.tmpdir(), "ts2rs-verify-"));
+    const rustPath = path.join(outputDir, "models.rs");
+    fs.writeFileSync(
+      rustPath,
+      fs.readFileSync(verifyRustPath, "utf-8")
+        .replace("pub id: u64,", "pub id: String,")
+        .replace("    pub note: String,", "    pub title: String,\n    #[serde(default)]\n    pub note: String,")
+        .replace("pub retries: Option<u32>,", "pub retries: u32,")
+        .replace("Urgent = 5,", "High,")
+        .replace("    Finished,", "    Done,")
+        .replace("    pub retries: u32,\n}", "    #[serde(skip)]\n    pub retries: u32,\n}")
+        .replace('#[serde(rename = "length")]', "#[serde(default)]") +
+        "\n#[derive(Serialize, Deserialize)]\npub struct Worker {\n    pub name: String,\n}\n",
+    );
+
+    const result = await verify({ entryFile: verifyPath }, rustPath);
+
+    expect(result.mismatches).toEqual([]);
+    expect(result.verifiedTypes).toContain("Worker");
+
+    fs.rmSync(outputDir, { recursive: true });
+  });
+});
+
+describe("convert - Constants", () => {
+  test("should convert `as const` enum-objects to enums named after the object keys", async () => {
+    const result = await convert({
+      entryFile: constantsPath,
+      typeNames: ["Account"],
+      strict: true,
+    });
+
+    expect(result.rustCode).toContain("pub enum Role");
+    expect(result.rustCode).toContain('#[serde(rename = "admin")]\n    Admin,');
+    expect(result.rustCode).toContain('#[serde(rename = "super-user")]\n    SuperUser,');
+    expect(result.rustCode).toContain('#[serde(rename = "read_only")]\n    ReadOnly,');
+    expect(result.rustCode).toContain("pub enum HttpStatus");
+    expect(result.rustCode).toContain('#[serde(rename = "404")]\n    NotFound,');
+    expect(result.rustCode).toContain("pub role: Role");
+    expect(result.rustCode).toContain("pub last_status: Option<HttpStatus>");
+    expect(result.rustCode).not.toContain("pub const");
+  });
+
+  test("should turn keys of enum-objects into valid and distinct variant names", async () => {
+    const result = await convert({
+      entryFile: constantsPath,
+      typeNames: ["ErrorCode"],
+      strict: true,
+    });
+
+    expect(result.rustCode).toContain('#[serde(rename = "unknown")]\n    Value1,');
+    expect(result.rustCode).toContain('#[serde(rename = "not-found")]\n    Value404,');
+    expect(result.rustCode).toContain('#[serde(rename = "missing")]\n    NotFound,');
+    expect(result.rustCode).toContain('#[serde(rename = "foo_bar")]\n    FooBar,');
+    expect(result.rustCode).toContain('#[serde(rename = "foo-bar")]\n    FooBar2,');
+  });
+
+  test("should convert exported literal constants to Rust constants", async () => {
+    const result = await convert({ entryFile: constantsPath });
+
+    expect(result.rustCode).toContain('/// Version of the public API\npub const API_VERSION: &str = "v2";');
+    expect(result.rustCode).toContain("pub const MAX_RETRIES: f64 = 3.0;");
+    expect(result.rustCode).toContain("pub const TIMEOUT_SECONDS: f64 = -1.5;");
+    expect(result.rustCode).toContain("pub const DEBUG: bool = false;");
+    expect(result.rustCode).toContain('pub const SUPPORTED_LOCALES: &[&str] = &["en", "fr", "de"];');
+    expect(result.rustCode).toContain("pub const BACKOFF_MS: &[f64] = &[100.0, 200.0, 400.0];");
+    expect(result.rustCode).toContain("use std::sync::LazyLock;");
+    expect(result.rustCode).toContain(
+      'pub static LIMITS: LazyLock<Value> = LazyLock::new(|| serde_json::json!({\n    "maxItems": 50,\n    "retry": {\n        "attempts": 3,\n        "backoff": [1, 2]\n    },\n    "label": null\n}));',
+    );
+    // Enum-objects are represented by their enum only
+    expect(result.rustCode).not.toContain("pub const ROLE");
+    expect(result.rustCode).not.toContain("COMPUTED");
+    expect(result.rustCode).not.toContain("HELPER");
+    expect(result.rustCode).not.toContain("MUTABLE");
+  });
+
+  test("should reject explicitly requested constants that are not literal data", async () => {
+    await expect(
+      convert({ entryFile: constantsPath, typeNames: ["computed"] }),
+    ).rejects.toThrow(TypeConversionError);
+  });
+});
+
 describe("convert - Serde Attributes", () => {
   test("should include serde derive by default", async () => {
     const result = await convert({
@@ -394,6 +1389,65 @@ describe("convert - Output File", () => {
     // Clean up
     fs.unlinkSync(outputPath);
   });
+
+  test("should write a module per source file when outputDir is specified", async () => {
+    const outputDir = path.join(fixturesDir, "src", "generated");
+    const fs = await import("fs");
+
+    const result = await convert({
+      entryFile: collisionsPath,
+      outputDir,
+    });
+
+    expect(Object.keys(result.files ?? {}).sort()).toEqual([
+      "billing/mod.rs",
+      "billing/status.rs",
+      "index.rs",
+      "mod.rs",
+      "status.rs",
+    ]);
+    expect(fs.readFileSync(path.join(outputDir, "mod.rs"), "utf-8")).toBe(result.rustCode);
+
+    // Same-named types keep their names in their own modules and are not re-exported
+    expect(result.rustCode).toContain("pub mod billing;\npub mod index;\npub mod status;");
+    expect(result.rustCode).toContain("pub use index::Order;");
+    expect(result.rustCode).not.toContain("Status");
+    expect(result.files!["billing/mod.rs"]).toContain("pub mod status;");
+    expect(result.files!["billing/status.rs"]).toContain("pub enum Status {");
+    expect(result.files!["billing/status.rs"]).not.toContain("Value");
+    expect(result.files!["status.rs"]).toContain("pub struct Status {");
+
+    const index = result.files!["index.rs"]!;
+    expect(index).toContain("use crate::generated::status::Status;");
+    expect(index).toContain("pub delivery: Status,");
+    expect(index).toContain("pub payment: crate::generated::billing::status::Status,");
+    expect(result.warnings).toEqual([]);
+
+    fs.rmSync(path.join(fixturesDir, "src"), { recursive: true });
+  });
+
+  test("should not let a mod.ts module overwrite the root mod.rs", async () => {
+    const result = await convert({ entryFile: modulesPath, outputDir: path.join(fixturesDir, "src", "generated"), dryRun: true });
+
+    expect(result.files!["mod_.rs"]).toContain("pub struct Settings {");
+    expect(result.files!["mod.rs"]).toContain("pub mod mod_;");
+    expect(result.files!["mod.rs"]).toContain("pub use mod_::Settings;");
+    expect(result.files!["index.rs"]).toContain("use crate::generated::mod_::Settings;");
+  });
+
+  test("should suffix files named the same module once sanitized", async () => {
+    const result = await convert({ entryFile: modulesPath, outputDir: path.join(fixturesDir, "src", "generated"), dryRun: true });
+
+    expect(Object.keys(result.files ?? {}).sort()).toEqual([
+      "index.rs",
+      "mod.rs",
+      "mod_.rs",
+      "user_profile.rs",
+      "user_profile2.rs",
+    ]);
+    expect(result.files!["user_profile.rs"]).toContain("pub struct Profile {");
+    expect(result.files!["user_profile2.rs"]).toContain("pub struct Avatar {");
+  });
 });
 
 describe("convert - Cross-package types", () => {
@@ -420,6 +1474,37 @@ describe("convert - Cross-package types", () => {
     expect(result.rustCode).toContain("pub assigned_to: UserRole");
     expect(result.rustCode).toContain("pub priority: Priority");
     expect(result.rustCode).toContain("pub test1: TestInterface");
-    expect(result.rustCode).toContain("pub test2: WorkspaceConfig");
+    expect(result.rustCode).toContain("pub test2: package_json::WorkspaceConfig");
+    expect(result.rustCode).toContain("pub mod package_json {");
+  });
+
+  test("should reference types from packages mapped to Rust crates instead of generating them", async () => {
+    const testPackage2Path = path.resolve(__dirname, "../../test-package2/src/index.ts");
+
+    const result = await convert({
+      entryFile: testPackage2Path,
+      packageCrates: { "test-package1": "shared_types", "type-fest": "type_fest" },
+    });
+
+    expect(result.rustCode).toContain("pub struct Task");
+    expect(result.rustCode).toContain("pub assigned_to: shared_types::UserRole");
+    expect(result.rustCode).toContain("pub priority: shared_types::Priority");
+    expect(result.rustCode).toContain("pub test1: shared_types::TestInterface");
+    expect(result.rustCode).toContain("pub test2: type_fest::package_json::WorkspaceConfig");
+    expect(result.rustCode).not.toContain("pub enum UserRole");
+    expect(result.rustCode).not.toContain("pub struct TestInterface");
+    expect(result.rustCode).not.toContain("pub mod package_json");
+
+    expect(result.externalTypes).toContainEqual({
+      rustPath: "shared_types::UserRole",
+      package: "test-package1",
+      usedBy: ["Task"],
+    });
+    expect(result.externalTypes.map((t) => t.rustPath).sort()).toEqual([
+      "shared_types::Priority",
+      "shared_types::TestInterface",
+      "shared_types::UserRole",
+      "type_fest::package_json::WorkspaceConfig",
+    ]);
   });
 });
@@ -123,4 +123,39 @@ fn test_0024() {
 #[test]
 fn test_0025() {
     run("0025");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_0027() {
+    run("0027");
+}
+
+#[test]
+fn test_0028() {
+    run("0028");
+}
+
+#[test]
+fn test_0029() {
+    run("0029");
+}
+
+#[test]
+fn test_0030() {
+    run("0030");
+}
+
+#[test]
+fn test_0031() {
+    run("0031");
+}
+
+#[test]
+fn test_0032() {
+    run("0032");
+}
+
+#[test]
+fn test_0033() {
+    run("0033");
+}
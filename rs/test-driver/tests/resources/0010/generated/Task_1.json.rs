@@ -1,5 +1,5 @@
 // This file is auto-generated by ts2rs. Do not edit manually.
-// Source: /workspaces/ts2rs/rs/test-driver/tests/resources/0010/types.ts
+// Source: rs/test-driver/tests/resources/0010/types.ts
 
 use serde::{Deserialize, Serialize};
 
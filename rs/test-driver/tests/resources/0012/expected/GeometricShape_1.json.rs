@@ -1,5 +1,5 @@
 // This file is auto-generated by ts2rs. Do not edit manually.
-// Source: /workspaces/ts-rs/rs/test-driver/tests/resources/0012/types.ts
+// Source: rs/test-driver/tests/resources/0012/types.ts
 
 use serde::{Deserialize, Serialize};
 
@@ -1,5 +1,5 @@
 // This file is auto-generated by ts2rs. Do not edit manually.
-// Source: /workspaces/ts-rs/rs/test-driver/tests/resources/0013/types.ts
+// Source: rs/test-driver/tests/resources/0013/types.ts
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
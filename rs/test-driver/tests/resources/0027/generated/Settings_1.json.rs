@@ -0,0 +1,33 @@
+// This file is auto-generated by ts2rs. Do not edit manually.
+// Source: rs/test-driver/tests/resources/0027/types.ts
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsLabels {
+    #[serde(rename = "en")]
+    pub en: String,
+    #[serde(rename = "fr")]
+    pub fr: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureFlags {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "darkMode")]
+    pub dark_mode: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "betaAccess")]
+    pub beta_access: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    pub labels: SettingsLabels,
+    pub flags: FeatureFlags,
+    pub limits: HashMap<String, f64>,
+}
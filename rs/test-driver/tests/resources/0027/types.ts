@@ -0,0 +1,11 @@
+export type Locale = "en" | "fr";
+
+export type Feature = "darkMode" | "betaAccess";
+
+export type FeatureFlags = { [K in Feature]?: boolean };
+
+export interface Settings {
+  labels: Record<Locale, string>;
+  flags: FeatureFlags;
+  limits: Record<string, number>;
+}
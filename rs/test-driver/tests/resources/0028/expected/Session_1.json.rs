@@ -0,0 +1,14 @@
+// This file is auto-generated by ts2rs. Do not edit manually.
+// Source: rs/test-driver/tests/resources/0028/types.ts
+
+use serde::{Deserialize, Serialize};
+
+pub type UserId = String;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub owner: UserId,
+    pub device: String,
+    pub token: String,
+}
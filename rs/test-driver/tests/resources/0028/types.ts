@@ -0,0 +1,14 @@
+export interface User {
+  kind: "user";
+  name: string;
+}
+
+export type UserId = `usr-${number}`;
+
+export type Id<T> = T extends User ? UserId : string;
+
+export interface Session {
+  owner: Id<User>;
+  device: Id<number>;
+  token: `tok_${string}`;
+}
@@ -0,0 +1,7 @@
+declare module "./types" {
+  interface Profile {
+    locale?: string;
+  }
+}
+
+export {};
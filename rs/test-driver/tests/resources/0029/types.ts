@@ -0,0 +1,10 @@
+import "./augment";
+
+export interface Profile {
+  id: string;
+  displayName: string;
+}
+
+export interface Profile {
+  avatarUrl: string | null;
+}
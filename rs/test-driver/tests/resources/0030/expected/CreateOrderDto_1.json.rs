@@ -0,0 +1,23 @@
+// This file is auto-generated by ts2rs. Do not edit manually.
+// Source: rs/test-driver/tests/resources/0030/types.ts
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOrderDto {
+    pub id: String,
+    #[serde(default = "CreateOrderDto::default_quantity")]
+    pub quantity: f64,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub customer: String,
+}
+
+impl CreateOrderDto {
+    fn default_quantity() -> f64 {
+        1.0
+    }
+}
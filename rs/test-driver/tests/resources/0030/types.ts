@@ -0,0 +1,18 @@
+export class BaseDto {
+  id!: string;
+}
+
+export class CreateOrderDto extends BaseDto {
+  quantity = 1;
+  note: string | null = null;
+  tags: string[] = [];
+  private internalRef?: string;
+
+  constructor(public customer: string) {
+    super();
+  }
+
+  total(price: number): number {
+    return price * this.quantity;
+  }
+}
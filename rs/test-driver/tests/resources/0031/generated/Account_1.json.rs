@@ -0,0 +1,31 @@
+// This file is auto-generated by ts2rs. Do not edit manually.
+// Source: rs/test-driver/tests/resources/0031/types.ts
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Role {
+    #[serde(rename = "admin")]
+    Admin,
+    #[serde(rename = "super-user")]
+    SuperUser,
+    #[serde(rename = "read_only")]
+    ReadOnly,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Plan {
+    #[serde(rename = "free")]
+    Free,
+    #[serde(rename = "pro")]
+    Pro,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    pub id: String,
+    pub role: Role,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<Plan>,
+}
@@ -0,0 +1,18 @@
+export const Role = {
+  Admin: "admin",
+  SuperUser: "super-user",
+  READ_ONLY: "read_only",
+} as const;
+export type Role = typeof Role[keyof typeof Role];
+
+export const Plan = {
+  Free: "free",
+  Pro: "pro",
+} as const;
+export type Plan = (typeof Plan)[keyof typeof Plan];
+
+export interface Account {
+  id: string;
+  role: Role;
+  plan?: Plan;
+}
@@ -0,0 +1,61 @@
+// This file is auto-generated by ts2rs. Do not edit manually.
+// Source: rs/test-driver/tests/resources/0032/types.ts
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Team {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub user: api::User,
+    pub admin: Option<admin::User>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub legacy: Option<api::v1::User>,
+}
+
+pub mod api {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub enum Role {
+        #[serde(rename = "admin")]
+        Admin,
+        #[serde(rename = "member")]
+        Member,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct User {
+        pub id: String,
+        pub team: super::Team,
+        pub role: Role,
+    }
+
+    pub mod v1 {
+        use super::super::*;
+
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct User {
+            #[serde(rename = "legacyId")]
+            pub legacy_id: f64,
+            pub current: super::User,
+        }
+    }
+}
+
+pub mod admin {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct User {
+        pub permissions: Vec<String>,
+    }
+}
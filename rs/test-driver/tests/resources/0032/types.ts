@@ -0,0 +1,32 @@
+export interface Team {
+  name: string;
+}
+
+export namespace Api {
+  export type Role = "admin" | "member";
+
+  export interface User {
+    id: string;
+    team: Team;
+    role: Role;
+  }
+
+  export namespace V1 {
+    export interface User {
+      legacyId: number;
+      current: Api.User;
+    }
+  }
+}
+
+export namespace Admin {
+  export interface User {
+    permissions: string[];
+  }
+}
+
+export interface Session {
+  user: Api.User;
+  admin: Admin.User | null;
+  legacy?: Api.V1.User;
+}
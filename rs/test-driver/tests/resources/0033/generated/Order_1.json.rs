@@ -0,0 +1,33 @@
+// This file is auto-generated by ts2rs. Do not edit manually.
+// Source: rs/test-driver/tests/resources/0033/types.ts
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Status {
+    pub carrier: String,
+    pub delivered: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub id: String,
+    pub delivery: Status,
+    pub payment: billing::Status,
+}
+
+pub mod billing {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub enum Status {
+        #[serde(rename = "pending")]
+        Pending,
+        #[serde(rename = "paid")]
+        Paid,
+        #[serde(rename = "refunded")]
+        Refunded,
+    }
+}
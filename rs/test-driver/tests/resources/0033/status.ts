@@ -0,0 +1,4 @@
+export interface Status {
+  carrier: string;
+  delivered: boolean;
+}
@@ -0,0 +1,8 @@
+import { Status } from "./status";
+import { Status as PaymentStatus } from "./billing/status";
+
+export interface Order {
+  id: string;
+  delivery: Status;
+  payment: PaymentStatus;
+}
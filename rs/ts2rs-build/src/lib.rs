@@ -0,0 +1,330 @@
+//! Generate Rust types from TypeScript types with [ts2rs](https://github.com/mcmah309/ts2rs) in a build script,
+//! instead of committing the generated code.
+//!
+//! ```no_run
+//! // build.rs
+//! ts2rs_build::Builder::new("../web/src/types.ts")
+//!     .types(["User", "Order"])
+//!     .mapping("Date", "chrono::DateTime<chrono::Utc>")
+//!     .generate()
+//!     .unwrap();
+//! ```
+//!
+//! ```ignore
+//! // src/lib.rs
+//! mod types {
+//!     include!(concat!(env!("OUT_DIR"), "/types.rs"));
+//! }
+//! ```
+//!
+//! The build script is run again when one of the TypeScript files the types were resolved from changes, and the
+//! warnings of ts2rs are shown as cargo warnings.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The ts2rs CLI in the `node_modules` of a project
+const PACKAGE_CLI: &str = "node_modules/ts2rs/dist/bundle/cli.bundle.js";
+
+/// JavaScript runtime running the ts2rs CLI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Runtime {
+    #[default]
+    Bun,
+    Node,
+}
+
+impl Runtime {
+    fn program(self) -> &'static str {
+        match self {
+            Runtime::Bun => "bun",
+            Runtime::Node => "node",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The ts2rs CLI isn't installed in the `node_modules` of the crate's directory or of its ancestors, see
+    /// [`Builder::cli`]
+    CliNotFound,
+    /// The runtime isn't installed, see [`Builder::runtime`]
+    RuntimeNotFound(Runtime),
+    /// `OUT_DIR` isn't set: [`Builder::generate`] runs in build scripts, unless given [`Builder::out_dir`]
+    NoOutDir,
+    /// The runtime couldn't be run, or the files couldn't be written
+    Io(io::Error),
+    /// ts2rs failed, with its error output
+    Failed(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CliNotFound => write!(f, "ts2rs not found in node_modules ({}), install it or set the path of its CLI", PACKAGE_CLI),
+            Error::RuntimeNotFound(runtime) => write!(f, "{} not found, install it or use another runtime", runtime.program()),
+            Error::NoOutDir => write!(f, "OUT_DIR is not set, generate from a build script or set `Builder::out_dir`"),
+            Error::Io(error) => write!(f, "failed to run ts2rs: {}", error),
+            Error::Failed(stderr) => write!(f, "ts2rs failed:\n{}", stderr.trim_end()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+/// A generated Rust file
+#[derive(Debug, Clone)]
+pub struct Output {
+    pub path: PathBuf,
+    /// Files the types were resolved from: the TypeScript files of the import graph and the tsconfig
+    pub input_files: Vec<PathBuf>,
+    pub warnings: Vec<String>,
+}
+
+/// Options of a conversion, run with [`Builder::generate`]
+#[derive(Debug, Clone)]
+pub struct Builder {
+    entry_file: PathBuf,
+    types: Vec<String>,
+    mappings: Vec<(String, String)>,
+    annotations: Vec<String>,
+    strict: bool,
+    runtime: Runtime,
+    cli: Option<PathBuf>,
+    out_dir: Option<PathBuf>,
+    output_file: String,
+}
+
+impl Builder {
+    /// Convert the types of a TypeScript file, relative to the crate's directory
+    pub fn new(entry_file: impl AsRef<Path>) -> Self {
+        Self {
+            entry_file: entry_file.as_ref().to_path_buf(),
+            types: Vec::new(),
+            mappings: Vec::new(),
+            annotations: Vec::new(),
+            strict: false,
+            runtime: Runtime::default(),
+            cli: None,
+            out_dir: None,
+            output_file: "types.rs".to_string(),
+        }
+    }
+
+    /// Names (or patterns, e.g. `Api*`) of the types to convert, all exported types by default
+    pub fn types<I, S>(mut self, types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.types.extend(types.into_iter().map(Into::into));
+        self
+    }
+
+    /// Use a Rust type for a TypeScript type, e.g. `("Date", "chrono::DateTime<chrono::Utc>")`
+    pub fn mapping(mut self, ts_type: impl Into<String>, rust_type: impl Into<String>) -> Self {
+        self.mappings.push((ts_type.into(), rust_type.into()));
+        self
+    }
+
+    /// Add an attribute before the `#[derive]` of every generated type, e.g. `#[derive(Hash)]`
+    pub fn annotation(mut self, annotation: impl Into<String>) -> Self {
+        self.annotations.push(annotation.into());
+        self
+    }
+
+    /// Fail on types that can't be converted instead of falling back to `serde_json::Value`
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Runtime running the CLI, bun by default
+    pub fn runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = runtime;
+        self
+    }
+
+    /// Path of the ts2rs CLI (`cli.bundle.js`, or `cli.bundle.ts` with bun), relative to the crate's directory.
+    /// By default it is looked up in the `node_modules` of the crate's directory and of its ancestors.
+    pub fn cli(mut self, cli: impl AsRef<Path>) -> Self {
+        self.cli = Some(cli.as_ref().to_path_buf());
+        self
+    }
+
+    /// Directory of the generated file, `OUT_DIR` by default
+    pub fn out_dir(mut self, out_dir: impl AsRef<Path>) -> Self {
+        self.out_dir = Some(out_dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Name of the generated file in the output directory, `types.rs` by default
+    pub fn output_file(mut self, output_file: impl Into<String>) -> Self {
+        self.output_file = output_file.into();
+        self
+    }
+
+    /// Generate the Rust file, returning its path. Prints `cargo:rerun-if-changed` for every file the types were
+    /// resolved from, and the warnings (or the error) of ts2rs as `cargo:warning`.
+    pub fn generate(&self) -> Result<PathBuf, Error> {
+        println!("cargo:rerun-if-changed={}", crate_dir()?.join(&self.entry_file).display());
+        let output = match self.convert() {
+            Ok(output) => output,
+            Err(error) => {
+                for line in error.to_string().lines() {
+                    println!("cargo:warning={}", line);
+                }
+                return Err(error);
+            }
+        };
+        for warning in &output.warnings {
+            println!("cargo:warning={}", warning);
+        }
+        for file in &output.input_files {
+            println!("cargo:rerun-if-changed={}", file.display());
+        }
+        Ok(output.path)
+    }
+
+    /// Generate the Rust file without printing anything
+    pub fn convert(&self) -> Result<Output, Error> {
+        let crate_dir = crate_dir()?;
+        let entry_file = crate_dir.join(&self.entry_file);
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => crate_dir.join(out_dir),
+            None => PathBuf::from(env::var_os("OUT_DIR").ok_or(Error::NoOutDir)?),
+        };
+        let cli = match &self.cli {
+            Some(cli) => crate_dir.join(cli),
+            None => find_package_cli(&crate_dir).ok_or(Error::CliNotFound)?,
+        };
+        fs::create_dir_all(&out_dir)?;
+
+        // Options are passed in a config file, since types and attributes may contain the separators of the flags
+        let output_path = out_dir.join(&self.output_file);
+        let config_path = out_dir.join(format!("{}.ts2rs.config.json", self.output_file));
+        let inputs_path = out_dir.join(format!("{}.inputs", self.output_file));
+        fs::write(&config_path, self.to_config(&entry_file, &output_path))?;
+
+        let output = Command::new(self.runtime.program())
+            .arg(&cli)
+            .arg("--config")
+            .arg(&config_path)
+            .arg("--inputs-file")
+            .arg(&inputs_path)
+            .output()
+            .map_err(|error| match error.kind() {
+                io::ErrorKind::NotFound => Error::RuntimeNotFound(self.runtime),
+                _ => Error::Io(error),
+            })?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            return Err(Error::Failed(stderr.into_owned()));
+        }
+
+        Ok(Output {
+            path: output_path,
+            input_files: fs::read_to_string(&inputs_path)?.lines().map(PathBuf::from).collect(),
+            warnings: parse_warnings(&stderr),
+        })
+    }
+
+    /// A `ts2rs.config.json` with a job converting to `output_path`
+    fn to_config(&self, entry_file: &Path, output_path: &Path) -> String {
+        let mut job = vec![
+            format!("\"entryFile\": {}", json_string(&entry_file.to_string_lossy())),
+            format!("\"outputPath\": {}", json_string(&output_path.to_string_lossy())),
+        ];
+        if !self.types.is_empty() {
+            job.push(format!("\"typeNames\": {}", json_array(&self.types)));
+        }
+        if !self.mappings.is_empty() {
+            let mappings = self
+                .mappings
+                .iter()
+                .map(|(ts_type, rust_type)| format!("{}: {}", json_string(ts_type), json_string(rust_type)))
+                .collect::<Vec<_>>();
+            job.push(format!("\"customTypeMappings\": {{ {} }}", mappings.join(", ")));
+        }
+        if !self.annotations.is_empty() {
+            job.push(format!("\"customTypeAnnotations\": {}", json_array(&self.annotations)));
+        }
+        if self.strict {
+            job.push("\"strict\": true".to_string());
+        }
+        format!("{{ \"jobs\": [{{ {} }}] }}\n", job.join(", "))
+    }
+}
+
+/// Directory relative paths are resolved against: the crate's, also when compiling it from the workspace's directory
+fn crate_dir() -> Result<PathBuf, Error> {
+    match env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => Ok(env::current_dir()?),
+    }
+}
+
+/// The ts2rs CLI in the `node_modules` of a directory or of its ancestors
+fn find_package_cli(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|ancestor| ancestor.join(PACKAGE_CLI))
+        .find(|cli| cli.is_file())
+}
+
+/// The warnings of the CLI's error output, e.g. `warning[TS2RS001]: Falling back to serde_json::Value: ...`, as
+/// `[TS2RS001] Falling back to serde_json::Value: ... (src/types.ts:6:3)` when followed by a location
+fn parse_warnings(stderr: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let mut lines = stderr.lines().peekable();
+    while let Some(line) = lines.next() {
+        let Some(warning) = line.find("warning[").map(|start| &line[start + "warning".len()..]) else {
+            continue;
+        };
+        let Some((code, message)) = warning.split_once("]: ") else {
+            continue;
+        };
+        let location = lines.peek().and_then(|next| next.trim_start().strip_prefix("--> "));
+        warnings.push(match location {
+            Some(location) => format!("{}] {} ({})", code, message, location),
+            None => format!("{}] {}", code, message),
+        });
+    }
+    warnings
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn json_array(values: &[String]) -> String {
+    let items = values.iter().map(|value| json_string(value)).collect::<Vec<_>>();
+    format!("[{}]", items.join(", "))
+}
@@ -0,0 +1,25 @@
+use std::fs;
+use ts2rs_build::Builder;
+
+#[test]
+fn generates_the_types_and_lists_their_files() {
+    let out_dir = std::env::temp_dir().join("ts2rs-build-test");
+    let output_path = Builder::new("tests/resources/types.ts")
+        .types(["User"])
+        .mapping("Date", "String")
+        .annotation("#[derive(Default)]")
+        .cli("../../js/ts2rs/src/cli.bundle.ts")
+        .out_dir(&out_dir)
+        .generate()
+        .expect("ts2rs failed");
+
+    let code = fs::read_to_string(&output_path).expect("Failed to read generated file");
+    assert!(code.contains("pub struct User {"));
+    assert!(code.contains("pub enum Role {"));
+    assert!(code.contains("pub created_at: String,"));
+    assert!(code.contains("#[derive(Default)]"));
+
+    let inputs = fs::read_to_string(out_dir.join("types.rs.inputs")).expect("Failed to read inputs file");
+    assert!(inputs.lines().any(|file| file.ends_with("tests/resources/types.ts")));
+    assert!(inputs.lines().any(|file| file.ends_with("tests/resources/role.ts")));
+}
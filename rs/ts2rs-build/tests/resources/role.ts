@@ -0,0 +1 @@
+export type Role = "admin" | "member";
@@ -0,0 +1,7 @@
+import type { Role } from "./role";
+
+export interface User {
+  id: string;
+  role: Role;
+  createdAt: Date;
+}
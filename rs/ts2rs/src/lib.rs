@@ -0,0 +1,314 @@
+//! Generate Rust types from TypeScript types at compile time with [ts2rs](https://github.com/mcmah309/ts2rs):
+//!
+//! ```ignore
+//! mod types {
+//!     ts2rs::include_ts!(
+//!         "../web/src/types.ts",
+//!         types = [User, Order, "Api*"],
+//!         mappings = { Date => chrono::DateTime<chrono::Utc> },
+//!         annotations = [#[derive(Hash)]],
+//!         strict = true,
+//!     );
+//! }
+//! ```
+//!
+//! The path is relative to the crate's directory. The crate is compiled again when one of the TypeScript files the
+//! types were resolved from changes. See [`ts2rs_build::Builder`] for generating the types from a build script
+//! instead.
+#![cfg_attr(feature = "nightly", feature(track_path))]
+
+use std::env;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{braced, bracketed, Attribute, Ident, LitBool, LitStr, Token, Type};
+use ts2rs_build::{Builder, Output, Runtime};
+
+/// Expand to the Rust types generated from a TypeScript file. Options:
+///
+/// - `types = [User, "Api*"]`: names or patterns of the types to convert, all exported types by default
+/// - `mappings = { Date => chrono::DateTime<chrono::Utc> }`: Rust types used for TypeScript types
+/// - `annotations = [#[derive(Hash)]]`: attributes added before the `#[derive]` of every type
+/// - `strict = true`: fail on types that can't be converted instead of using `serde_json::Value`
+/// - `runtime = node`: run the ts2rs CLI with node instead of bun
+/// - `cli = "node_modules/ts2rs/dist/bundle/cli.bundle.js"`: path of the CLI, looked up in `node_modules` by default
+///
+/// The generated code imports `serde`, so it is best included in its own module. Warnings of ts2rs are reported
+/// at the invocation as deprecation warnings, since proc macros can't emit warnings on stable (with `strict = true`,
+/// types that would have been warned about fail the compilation instead).
+#[proc_macro]
+pub fn include_ts(input: TokenStream) -> TokenStream {
+    let out_dir = get_out_dir(&input.to_string());
+    let args = syn::parse_macro_input!(input as IncludeTs);
+    match expand(args.builder, &out_dir) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Convert in `out_dir`, which is removed once the generated code is read
+fn expand(builder: Builder, out_dir: &Path) -> syn::Result<proc_macro2::TokenStream> {
+    let error = |message: String| syn::Error::new(Span::call_site(), message);
+    let generated = generate(builder.out_dir(out_dir));
+    let _ = fs::remove_dir_all(out_dir);
+    let (output, code) = generated.map_err(error)?;
+    let items: proc_macro2::TokenStream =
+        code.parse().map_err(|e| error(format!("ts2rs generated invalid Rust code: {}", e)))?;
+    let tracked = track_files(&output.input_files);
+    let warnings = output.warnings.iter().map(|warning| warn(warning));
+    Ok(quote! {
+        #items
+        #tracked
+        #(#warnings)*
+    })
+}
+
+/// Run ts2rs, returning its output and the generated code
+fn generate(builder: Builder) -> Result<(Output, String), String> {
+    let output = builder.convert().map_err(|e| e.to_string())?;
+    let code = fs::read_to_string(&output.path).map_err(|e| format!("failed to read the generated code: {}", e))?;
+    Ok((output, code))
+}
+
+/// A warning at the invocation: the use of a deprecated constant whose note is the message
+fn warn(message: &str) -> proc_macro2::TokenStream {
+    let message = LitStr::new(message, Span::call_site());
+    quote! {
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const ts2rs_warning: () = ();
+            ts2rs_warning
+        };
+    }
+}
+
+/// Recompile the invoking crate when one of the files changes
+#[cfg(feature = "nightly")]
+fn track_files(files: &[PathBuf]) -> proc_macro2::TokenStream {
+    for file in files {
+        proc_macro::tracked_path::path(&*file.to_string_lossy());
+    }
+    proc_macro2::TokenStream::new()
+}
+
+/// Recompile the invoking crate when one of the files changes, by including them
+#[cfg(not(feature = "nightly"))]
+fn track_files(files: &[PathBuf]) -> proc_macro2::TokenStream {
+    let files = files.iter().map(|file| LitStr::new(&file.to_string_lossy(), Span::call_site()));
+    quote! {
+        #(const _: &[u8] = include_bytes!(#files);)*
+    }
+}
+
+/// A directory for the files of an expansion, in `OUT_DIR` if the invoking crate has a build script. It is unique to
+/// the expansion, since the same invocation may be expanded concurrently (e.g. by `cargo check` and rust-analyzer).
+fn get_out_dir(input: &str) -> PathBuf {
+    static EXPANSIONS: AtomicUsize = AtomicUsize::new(0);
+    let mut hasher = DefaultHasher::new();
+    env::var("CARGO_MANIFEST_DIR").unwrap_or_default().hash(&mut hasher);
+    input.hash(&mut hasher);
+    let expansion = EXPANSIONS.fetch_add(1, Ordering::Relaxed);
+    env::var_os("OUT_DIR")
+        .map_or_else(env::temp_dir, PathBuf::from)
+        .join("ts2rs-include")
+        .join(format!("{:016x}-{}-{}", hasher.finish(), process::id(), expansion))
+}
+
+/// Arguments of `include_ts!`: the entry file, then options
+struct IncludeTs {
+    builder: Builder,
+}
+
+impl Parse for IncludeTs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let entry_file: LitStr = input.parse()?;
+        let mut builder = Builder::new(entry_file.value());
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let option: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            builder = match option.to_string().as_str() {
+                "types" => {
+                    let content;
+                    bracketed!(content in input);
+                    let types = Punctuated::<TypeName, Token![,]>::parse_terminated(&content)?;
+                    builder.types(types.into_iter().map(|type_name| type_name.0))
+                }
+                "mappings" => {
+                    let content;
+                    braced!(content in input);
+                    let mappings = Punctuated::<Mapping, Token![,]>::parse_terminated(&content)?;
+                    mappings.into_iter().fold(builder, |builder, mapping| {
+                        let rust_type = &mapping.rust_type;
+                        builder.mapping(mapping.ts_type, quote!(#rust_type).to_string())
+                    })
+                }
+                "annotations" => {
+                    let content;
+                    bracketed!(content in input);
+                    Attribute::parse_outer(&content)?
+                        .into_iter()
+                        .fold(builder, |builder, attribute| builder.annotation(quote!(#attribute).to_string()))
+                }
+                "strict" => builder.strict(input.parse::<LitBool>()?.value),
+                "runtime" => {
+                    let runtime: Ident = input.parse()?;
+                    builder.runtime(match runtime.to_string().as_str() {
+                        "bun" => Runtime::Bun,
+                        "node" => Runtime::Node,
+                        _ => return Err(syn::Error::new(runtime.span(), "expected `bun` or `node`")),
+                    })
+                }
+                "cli" => builder.cli(input.parse::<LitStr>()?.value()),
+                _ => {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "unknown option, expected `types`, `mappings`, `annotations`, `strict`, `runtime` or `cli`",
+                    ))
+                }
+            };
+        }
+        Ok(Self { builder })
+    }
+}
+
+/// A type name (`User`) or pattern (`"Api*"`)
+struct TypeName(String);
+
+impl Parse for TypeName {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            Ok(Self(input.parse::<LitStr>()?.value()))
+        } else {
+            Ok(Self(input.parse::<Ident>()?.to_string()))
+        }
+    }
+}
+
+/// `TypeScriptName => RustType`
+struct Mapping {
+    ts_type: String,
+    rust_type: Type,
+}
+
+impl Parse for Mapping {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ts_type: Ident = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        Ok(Self { ts_type: ts_type.to_string(), rust_type: input.parse()? })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLI: &str = "../../js/ts2rs/src/cli.bundle.ts";
+
+    fn parse(input: &str) -> syn::Result<Builder> {
+        syn::parse_str::<IncludeTs>(input).map(|args| args.builder)
+    }
+
+    #[test]
+    fn parses_the_options() {
+        let builder = parse(
+            r#""types.ts", types = [User, "Api*"], mappings = { Date => chrono::DateTime<chrono::Utc> },
+            annotations = [#[derive(Hash)]], strict = true, runtime = node, cli = "cli.js","#,
+        )
+        .expect("failed to parse the options");
+
+        let expected = Builder::new("types.ts")
+            .types(["User", "Api*"])
+            .mapping("Date", "chrono :: DateTime < chrono :: Utc >")
+            .annotation("# [derive (Hash)]")
+            .strict(true)
+            .runtime(Runtime::Node)
+            .cli("cli.js");
+        assert_eq!(format!("{:?}", builder), format!("{:?}", expected));
+    }
+
+    #[test]
+    fn rejects_unknown_options() {
+        let error = parse(r#""types.ts", color = true"#).expect_err("an unknown option was accepted");
+        assert!(error.to_string().starts_with("unknown option"));
+
+        let error = parse(r#""types.ts", runtime = deno"#).expect_err("an unknown runtime was accepted");
+        assert_eq!(error.to_string(), "expected `bun` or `node`");
+    }
+
+    #[test]
+    fn gives_each_expansion_its_own_out_dir() {
+        let out_dir = get_out_dir(r#""types.ts", types = [User]"#);
+        let base = env::var_os("OUT_DIR").map_or_else(env::temp_dir, PathBuf::from);
+        assert_eq!(out_dir.parent(), Some(base.join("ts2rs-include").as_path()));
+        assert_ne!(out_dir, get_out_dir(r#""types.ts", types = [User]"#));
+        assert_ne!(out_dir, get_out_dir(r#""types.ts", types = [Order]"#));
+    }
+
+    #[cfg(not(feature = "nightly"))]
+    #[test]
+    fn includes_the_tracked_files() {
+        let tracked = track_files(&[PathBuf::from("/web/src/types.ts"), PathBuf::from("/web/src/role.ts")]);
+        assert_eq!(
+            tracked.to_string(),
+            quote! {
+                const _: &[u8] = include_bytes!("/web/src/types.ts");
+                const _: &[u8] = include_bytes!("/web/src/role.ts");
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn expands_to_the_generated_types() {
+        let builder = parse(&format!(
+            r#""../ts2rs-build/tests/resources/types.ts", types = [User], mappings = {{ Date => String }}, cli = "{}""#,
+            CLI
+        ))
+        .expect("failed to parse the options");
+        let out_dir = get_out_dir("expands_to_the_generated_types");
+        let tokens = expand(builder, &out_dir).expect("ts2rs failed");
+        assert!(!out_dir.exists());
+
+        let code = tokens.to_string();
+        assert!(code.contains("pub struct User {"));
+        assert!(code.contains("pub enum Role {"));
+        assert!(code.contains("pub created_at : String"));
+        assert!(code.contains("ts2rs-build/tests/resources/role.ts\")"));
+        assert!(!code.contains("deprecated"));
+    }
+
+    #[test]
+    fn reports_warnings_as_deprecations() {
+        let builder =
+            parse(&format!(r#""tests/resources/warnings.ts", cli = "{}""#, CLI)).expect("failed to parse the options");
+        let tokens = expand(builder, &get_out_dir("reports_warnings_as_deprecations")).expect("ts2rs failed");
+
+        let code = tokens.to_string();
+        assert!(code.contains("pub priority : Value"));
+        assert!(code.contains("# [deprecated (note = \"[TS2RS001] Falling back to serde_json::Value"));
+        assert!(code.contains("warnings.ts:3:3)\")]"));
+    }
+
+    #[test]
+    fn fails_on_warnings_when_strict() {
+        let builder = parse(&format!(r#""tests/resources/warnings.ts", strict = true, cli = "{}""#, CLI))
+            .expect("failed to parse the options");
+        let out_dir = get_out_dir("fails_on_warnings_when_strict");
+        let error = expand(builder, &out_dir).expect_err("the conversion succeeded");
+        assert!(!out_dir.exists());
+        assert!(error.to_string().contains("TS2RS001"));
+    }
+}
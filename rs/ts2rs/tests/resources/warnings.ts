@@ -0,0 +1,4 @@
+export interface Job {
+  id: string;
+  priority: "low" | "high";
+}